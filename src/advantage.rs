@@ -0,0 +1,139 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::frames::get_tag_matcher;
+
+/// A parsed on block or on hit cell from a frame data table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Advantage {
+    /// The most favourable frame advantage listed, e.g. `+10` in `-20 (+10)`
+    pub best: Option<i32>,
+    /// The least favourable frame advantage listed, e.g. `-20` in `-20 (+10)`
+    pub worst: Option<i32>,
+    pub effect: Option<AdvantageEffect>,
+}
+
+/// What happens to the opponent when the frame advantage is not (only) a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdvantageEffect {
+    Knockdown,
+    WallBounce,
+    WallStick,
+    Launch,
+    /// The advantage depends on spacing, version or timing and Dustloop does not give a number
+    Varies,
+}
+
+fn get_number_matcher() -> &'static Regex {
+    static NUMBER_MATCHER: OnceLock<Regex> = OnceLock::new();
+    NUMBER_MATCHER.get_or_init(|| Regex::new(r"[+-]?\d+").unwrap())
+}
+
+fn get_effect_matchers() -> &'static [(Regex, AdvantageEffect)] {
+    static EFFECT_MATCHERS: OnceLock<Vec<(Regex, AdvantageEffect)>> = OnceLock::new();
+    EFFECT_MATCHERS.get_or_init(|| vec![
+        (Regex::new(r"(?i)(wall\s*bounce|\bWB\b)").unwrap(), AdvantageEffect::WallBounce),
+        (Regex::new(r"(?i)(wall\s*stick|wall\s*splat|\bWS\b)").unwrap(), AdvantageEffect::WallStick),
+        (Regex::new(r"(?i)(\bH?KD\b|knock\s*down)").unwrap(), AdvantageEffect::Knockdown),
+        (Regex::new(r"(?i)(launch)").unwrap(), AdvantageEffect::Launch),
+        (Regex::new(r"(?i)(varies|\?)").unwrap(), AdvantageEffect::Varies),
+    ])
+}
+
+impl Advantage {
+    /// Parses a cell as scraped from Dustloop. Any markup in the cell is ignored.
+    pub fn parse(raw: &str) -> Advantage {
+        let text = get_tag_matcher().replace_all(raw, "");
+        let numbers = get_number_matcher().find_iter(&text)
+            .filter(|m| is_advantage_value(&text, m.start(), m.end()))
+            .filter_map(|m| m.as_str().trim_start_matches('+').parse::<i32>().ok())
+            .collect::<Vec<i32>>();
+        let effect = get_effect_matchers().iter()
+            .find(|(regex, _)| regex.is_match(&text))
+            .map(|(_, effect)| *effect);
+        Advantage {
+            best: numbers.iter().copied().max(),
+            worst: numbers.iter().copied().min(),
+            effect,
+        }
+    }
+
+    /// Whether the move leaves the attacker at an advantage even in its worst listed case
+    pub fn is_plus(&self) -> bool {
+        self.worst.is_some_and(|v| v > 0)
+    }
+
+    /// Whether the worst listed advantage is at least `threshold`, e.g. `is_safe_on_block(-4)` against 5 frame punishes.
+    /// Moves without a numeric advantage are never considered safe.
+    pub fn is_safe_on_block(&self, threshold: i32) -> bool {
+        self.worst.is_some_and(|v| v >= threshold)
+    }
+
+    /// Whether the opponent is knocked down, wall stuck, wall bounced or launched
+    pub fn is_knockdown_like(&self) -> bool {
+        matches!(self.effect, Some(AdvantageEffect::Knockdown | AdvantageEffect::WallBounce | AdvantageEffect::WallStick | AdvantageEffect::Launch))
+    }
+}
+
+// signed numbers and numbers standing on their own are advantages, the numbers of labels like `Lv2`, `Hits 2` or
+// `2 hits` are not
+fn is_advantage_value(text: &str, start: usize, end: usize) -> bool {
+    let glued_before = text[..start].chars().next_back().is_some_and(char::is_alphabetic);
+    if glued_before {
+        return false;
+    }
+    if text[start..].starts_with(['+', '-']) {
+        return true;
+    }
+    let before_word = text[end..].trim_start().chars().next().is_some_and(char::is_alphabetic);
+    let after_word = text[..start].trim_end().chars().next_back().is_some_and(char::is_alphabetic);
+    !before_word && !after_word
+}
+
+#[test]
+fn parse_numeric_advantage() {
+    let advantage = Advantage::parse("-20 (+10)");
+    assert_eq!(advantage.worst, Some(-20));
+    assert_eq!(advantage.best, Some(10));
+    assert!(!advantage.is_plus());
+    assert!(!advantage.is_safe_on_block(-4));
+
+    let advantage = Advantage::parse("+2");
+    assert!(advantage.is_plus());
+    assert!(advantage.is_safe_on_block(-4));
+    assert_eq!(Advantage::parse("-5~-3").best, Some(-3));
+    assert_eq!(Advantage::parse(""), Advantage::default());
+    assert_eq!(Advantage::parse("0").best, Some(0));
+}
+
+#[test]
+fn parse_labelled_advantage() {
+    let advantage = Advantage::parse("-12 (Hits 2)");
+    assert_eq!((advantage.worst, advantage.best), (Some(-12), Some(-12)));
+    assert!(!advantage.is_safe_on_block(-4));
+
+    let advantage = Advantage::parse("-2 (Lv2: +1)");
+    assert_eq!((advantage.worst, advantage.best), (Some(-2), Some(1)));
+    assert!(!advantage.is_plus());
+    assert!(advantage.is_safe_on_block(-4));
+
+    let advantage = Advantage::parse("Lv1: -6<br>Lv2: -3<br>Lv3: +2");
+    assert_eq!((advantage.worst, advantage.best), (Some(-6), Some(2)));
+    assert_eq!(Advantage::parse("-5 (2 hits)").best, Some(-5));
+    assert_eq!(Advantage::parse("-3 (3 hits)").best, Some(-3));
+}
+
+#[test]
+fn parse_advantage_effects() {
+    assert_eq!(Advantage::parse("KD").effect, Some(AdvantageEffect::Knockdown));
+    assert_eq!(Advantage::parse("+30 HKD").effect, Some(AdvantageEffect::Knockdown));
+    assert_eq!(Advantage::parse("Wall Stick").effect, Some(AdvantageEffect::WallStick));
+    assert_eq!(Advantage::parse("Wall Bounce").effect, Some(AdvantageEffect::WallBounce));
+    assert_eq!(Advantage::parse("Launch").effect, Some(AdvantageEffect::Launch));
+    assert_eq!(Advantage::parse("Varies").effect, Some(AdvantageEffect::Varies));
+    assert!(Advantage::parse("KD").is_knockdown_like());
+    assert!(!Advantage::parse("KD").is_safe_on_block(-4));
+}
@@ -0,0 +1,170 @@
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::{CharacterId, GGSTDLError, Roster};
+
+/// Move aliases added on top of the built in ones, e.g. community slang or other languages.
+/// Pass them to [`crate::GGSTDLData::find_move_with_aliases`] and friends, or keep them on a [`crate::GGSTDLHandle`].
+#[derive(Debug, Clone, Default)]
+pub struct MoveAliases {
+    pub entries: Vec<MoveAlias>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveAlias {
+    pub character: CharacterId,
+    /// Matched against the whole query
+    pub regex: Regex,
+    /// The input or name of the move the alias is for, e.g. `c.S` or `Fafnir`
+    pub target: String,
+    pub priority: AliasPriority,
+}
+
+/// When a user alias is tried relative to the built in aliases
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum AliasPriority {
+    /// Before the built in aliases, so it wins when both match
+    #[default]
+    Override,
+    /// Only when no built in alias matches
+    Fallback,
+}
+
+impl MoveAliases {
+    pub fn new() -> MoveAliases {
+        MoveAliases::default()
+    }
+
+    /// Adds a literal alias, matched case insensitively against the whole query, e.g. `pilebunker` for `Fafnir`
+    pub fn add(&mut self, character: CharacterId, alias: &str, target: impl Into<String>, priority: AliasPriority) {
+        let regex = Regex::new(&format!(r"(?i)^\s*{}\s*$", regex::escape(alias.trim()))).unwrap();
+        self.entries.push(MoveAlias { character, regex, target: target.into(), priority });
+    }
+
+    /// Adds an alias regex, e.g. `(?i)^pile\s*bunker$`
+    pub fn add_pattern(&mut self, character: CharacterId, pattern: &str, target: impl Into<String>, priority: AliasPriority) -> Result<(), GGSTDLError> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| GGSTDLError::InvalidAliasRegex { pattern: pattern.to_string(), message: e.to_string() })?;
+        self.entries.push(MoveAlias { character, regex, target: target.into(), priority });
+        Ok(())
+    }
+
+    /// Adds every alias of `other` after the ones already here
+    pub fn merge(&mut self, other: MoveAliases) {
+        self.entries.extend(other.entries);
+    }
+
+    /// The aliases of a character with the given priority that match the query, in the order they were added
+    pub(crate) fn matching<'a>(&'a self, character: &'a CharacterId, priority: AliasPriority, query: &'a str) -> impl Iterator<Item = &'a MoveAlias> {
+        self.entries.iter().filter(move |a| &a.character == character && a.priority == priority && a.regex.is_match(query))
+    }
+
+    /// Reads an alias file, see [`MoveAliases::from_toml`]
+    pub async fn from_file(path: impl AsRef<Path>) -> Result<MoveAliases, GGSTDLError> {
+        MoveAliases::from_file_with_roster(path, &Roster::builtin()).await
+    }
+
+    /// Reads an alias file for the characters of `roster`, see [`MoveAliases::from_toml_with_roster`]
+    pub async fn from_file_with_roster(path: impl AsRef<Path>, roster: &Roster) -> Result<MoveAliases, GGSTDLError> {
+        let path = path.as_ref();
+        let text = tokio::fs::read_to_string(path).await
+            .map_err(|e| GGSTDLError::Io { path: path.display().to_string(), message: e.to_string() })?;
+        MoveAliases::from_toml_with_roster(&text, roster)
+    }
+
+    /// Reads a TOML alias file (needs the `toml` feature), one `[[aliases]]` table per alias:
+    /// `character = "sol"`, `alias = "pilebunker"` (or a `pattern` regex), `move = "Fafnir"` and optionally
+    /// `priority = "fallback"` (`"override"` by default). The character is found in the built in roster by name, id
+    /// or alias, like [`crate::GGSTDLData::find_character`] does.
+    pub fn from_toml(text: &str) -> Result<MoveAliases, GGSTDLError> {
+        MoveAliases::from_toml_with_roster(text, &Roster::builtin())
+    }
+
+    /// Reads a TOML alias file like [`MoveAliases::from_toml`], finding the characters in `roster`.
+    /// A character that matches no entry, or several equally well, is an error.
+    pub fn from_toml_with_roster(text: &str, roster: &Roster) -> Result<MoveAliases, GGSTDLError> {
+        #[cfg(feature = "toml")]
+        {
+            let file: file::AliasFile = toml::from_str(text).map_err(|e| GGSTDLError::InvalidToml { message: e.to_string() })?;
+            file.into_aliases(roster)
+        }
+        #[cfg(not(feature = "toml"))]
+        {
+            let _ = (text, roster);
+            Err(GGSTDLError::InvalidToml { message: String::from("reading TOML alias files needs the toml feature") })
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+mod file {
+    use serde::Deserialize;
+
+    use super::{AliasPriority, MoveAliases};
+    use crate::{GGSTDLError, Roster};
+
+    #[derive(Deserialize)]
+    pub struct AliasFile {
+        #[serde(default)]
+        aliases: Vec<AliasFileEntry>,
+    }
+
+    #[derive(Deserialize)]
+    struct AliasFileEntry {
+        character: String,
+        alias: Option<String>,
+        pattern: Option<String>,
+        #[serde(rename = "move")]
+        target: String,
+        #[serde(default)]
+        priority: AliasPriority,
+    }
+
+    impl AliasFile {
+        pub fn into_aliases(self, roster: &Roster) -> Result<MoveAliases, GGSTDLError> {
+            let mut aliases = MoveAliases::new();
+            for entry in self.aliases {
+                let character = roster.find_entry(&entry.character)?.id.clone();
+                match (entry.alias, entry.pattern) {
+                    (_, Some(pattern)) => aliases.add_pattern(character, &pattern, entry.target, entry.priority)?,
+                    (Some(alias), None) => aliases.add(character, &alias, entry.target, entry.priority),
+                    (None, None) => return Err(GGSTDLError::InvalidToml {
+                        message: format!("alias for '{}' needs an alias or a pattern", entry.target),
+                    }),
+                }
+            }
+            Ok(aliases)
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn alias_file_toml() {
+    let aliases = MoveAliases::from_toml(r#"
+        [[aliases]]
+        character = "sol"
+        alias = "pilebunker"
+        move = "Fafnir"
+
+        [[aliases]]
+        character = "Sol Badguy"
+        pattern = "(?i)^gf$"
+        move = "Gun Flame"
+        priority = "fallback"
+    "#).unwrap();
+    assert_eq!(aliases.entries.len(), 2);
+    assert_eq!(aliases.matching(&CharacterId::SOL, AliasPriority::Override, "PileBunker").count(), 1);
+    assert_eq!(aliases.entries[1].priority, AliasPriority::Fallback);
+    assert_eq!(aliases.entries[1].character, CharacterId::SOL);
+    assert!(MoveAliases::from_toml("[[aliases]]\ncharacter = \"sol\"\nmove = \"Fafnir\"").is_err());
+    let unknown = MoveAliases::from_toml("[[aliases]]\ncharacter = \"nobody\"\nalias = \"pb\"\nmove = \"Fafnir\"");
+    assert_eq!(unknown.unwrap_err(), GGSTDLError::UnknownCharacter { query: String::from("nobody") });
+
+    let mut roster = Roster::builtin();
+    roster.entries.push(crate::RosterEntry::new(CharacterId::Other(String::from("SLAYER")), "Slayer", r"(?i)(dandy)", ""));
+    let aliases = MoveAliases::from_toml_with_roster("[[aliases]]\ncharacter = \"dandy\"\nalias = \"mappa\"\nmove = \"Mappa Hunch\"", &roster).unwrap();
+    assert_eq!(aliases.entries[0].character, CharacterId::Other(String::from("SLAYER")));
+}
@@ -0,0 +1,11 @@
+// Saves every character's dustloop frame data and overview pages, and the GGST index page, into a directory for
+// ggstdl::load_from_dir and ggstdl::discover
+// usage: ggstdl-snapshot [directory]
+#[tokio::main]
+async fn main() {
+    let path = std::env::args().nth(1).unwrap_or(String::from("snapshot"));
+    if let Err(e) = ggstdl::snapshot(&path).await {
+        eprintln!("Error saving snapshot to {}: {}", path, e);
+        std::process::exit(1);
+    }
+}
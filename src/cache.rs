@@ -0,0 +1,210 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+
+use crate::{DirSource, FetchResult, GGSTDLError, Page, PageRequest, PageSource, Revalidated, Validators};
+
+/// Keeps a copy of every page fetched through another source on disk.
+///
+/// Copies younger than the TTL are served without touching the inner source, older ones are revalidated with
+/// `If-None-Match`/`If-Modified-Since` and served stale when the inner source fails. Pages are stored in the same
+/// layout as [`crate::snapshot`], so the cache directory can also be read with [`crate::load_from_dir`].
+#[derive(Debug, Clone)]
+pub struct CachedSource<S> {
+    inner: S,
+    dir: DirSource,
+    path: PathBuf,
+    ttl: Duration,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Metadata {
+    /// Since the unix epoch, written as fractional seconds so sub-second TTLs work
+    fetched_at: Duration,
+    status: Option<u16>,
+    validators: Validators,
+}
+
+impl<S: PageSource> CachedSource<S> {
+    pub fn new(inner: S, path: impl AsRef<Path>, ttl: Duration) -> CachedSource<S> {
+        CachedSource {
+            inner,
+            dir: DirSource::new(path.as_ref()),
+            path: path.as_ref().to_path_buf(),
+            ttl,
+        }
+    }
+
+    fn metadata_path(&self, request: &PageRequest) -> PathBuf {
+        self.path.join(format!("{}.meta", DirSource::file_name(request)))
+    }
+
+    async fn read(&self, request: &PageRequest) -> Option<(Page, Metadata)> {
+        let body = tokio::fs::read_to_string(self.dir.path_for(request)).await.ok()?;
+        let metadata = Metadata::parse(&tokio::fs::read_to_string(self.metadata_path(request)).await.ok()?);
+        let page = Page {
+            body,
+            status: metadata.status,
+            etag: metadata.validators.etag.clone(),
+            last_modified: metadata.validators.last_modified.clone(),
+        };
+        Some((page, metadata))
+    }
+
+    async fn write(&self, request: &PageRequest, page: &Page) -> Result<(), GGSTDLError> {
+        let io_error = |path: &Path, e: std::io::Error| GGSTDLError::Io { path: path.display().to_string(), message: e.to_string() };
+        tokio::fs::create_dir_all(&self.path).await.map_err(|e| io_error(&self.path, e))?;
+        let metadata = Metadata {
+            fetched_at: now(),
+            status: page.status,
+            validators: Validators { etag: page.etag.clone(), last_modified: page.last_modified.clone() },
+        };
+        let body_path = self.dir.path_for(request);
+        tokio::fs::write(&body_path, &page.body).await.map_err(|e| io_error(&body_path, e))?;
+        let metadata_path = self.metadata_path(request);
+        tokio::fs::write(&metadata_path, metadata.to_string()).await.map_err(|e| io_error(&metadata_path, e))
+    }
+}
+
+#[async_trait]
+impl<S: PageSource> PageSource for CachedSource<S> {
+    async fn fetch(&self, request: &PageRequest) -> FetchResult {
+        let Some((cached, metadata)) = self.read(request).await else {
+            let page = self.inner.fetch(request).await?;
+            if page.is_success() {
+                // a cache that can't be written to just means the next fetch goes to the inner source again
+                let _ = self.write(request, &page).await;
+            }
+            return Ok(page);
+        };
+
+        if now().saturating_sub(metadata.fetched_at) < self.ttl {
+            return Ok(cached);
+        }
+
+        match self.inner.fetch_if_modified(request, &metadata.validators).await {
+            Ok(Revalidated::NotModified) => {
+                let _ = self.write(request, &cached).await;
+                Ok(cached)
+            },
+            Ok(Revalidated::Modified(page)) if page.is_success() => {
+                let _ = self.write(request, &page).await;
+                Ok(page)
+            },
+            // the site is down or erroring, a stale copy is better than nothing
+            _ => Ok(cached),
+        }
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+impl Metadata {
+    fn parse(text: &str) -> Metadata {
+        let mut metadata = Metadata::default();
+        for (key, value) in text.lines().filter_map(|l| l.split_once('=')) {
+            match key {
+                "fetched_at" => metadata.fetched_at = value.parse::<f64>().ok()
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .unwrap_or_default(),
+                "status" => metadata.status = value.parse().ok(),
+                "etag" => metadata.validators.etag = Some(value.to_string()),
+                "last_modified" => metadata.validators.last_modified = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        metadata
+    }
+}
+
+impl std::fmt::Display for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "fetched_at={}.{:09}", self.fetched_at.as_secs(), self.fetched_at.subsec_nanos())?;
+        if let Some(status) = self.status {
+            writeln!(f, "status={}", status)?;
+        }
+        if let Some(etag) = &self.validators.etag {
+            writeln!(f, "etag={}", etag)?;
+        }
+        if let Some(last_modified) = &self.validators.last_modified {
+            writeln!(f, "last_modified={}", last_modified)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+struct CountingSource {
+    calls: std::sync::atomic::AtomicUsize,
+    revalidations: std::sync::atomic::AtomicUsize,
+    down: std::sync::atomic::AtomicBool,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl PageSource for CountingSource {
+    async fn fetch(&self, request: &PageRequest) -> FetchResult {
+        use std::sync::atomic::Ordering;
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.down.load(Ordering::SeqCst) {
+            return Err(GGSTDLError::Fetch { url: request.url.clone(), message: String::from("down") });
+        }
+        let mut page = Page::new("fresh page");
+        page.status = Some(200);
+        page.etag = Some(String::from("\"v1\""));
+        Ok(page)
+    }
+
+    async fn fetch_if_modified(&self, request: &PageRequest, validators: &Validators) -> Result<Revalidated, GGSTDLError> {
+        self.revalidations.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        if validators.etag.as_deref() == Some("\"v1\"") && !self.down.load(std::sync::atomic::Ordering::SeqCst) {
+            return Ok(Revalidated::NotModified);
+        }
+        self.fetch(request).await.map(Revalidated::Modified)
+    }
+}
+
+#[tokio::test]
+async fn cache_ttl_and_revalidation() {
+    use std::sync::atomic::Ordering;
+    let dir = tempfile::tempdir().unwrap();
+    let request = PageRequest::character(crate::CharacterId::SOL, "https://www.dustloop.com/w/GGST/Sol_Badguy/Frame_Data");
+    let inner = CountingSource { calls: Default::default(), revalidations: Default::default(), down: Default::default() };
+
+    let cached = CachedSource::new(inner, dir.path(), Duration::from_secs(3600));
+    assert_eq!(cached.fetch(&request).await.unwrap().body, "fresh page");
+    assert_eq!(cached.fetch(&request).await.unwrap().body, "fresh page");
+    assert_eq!(cached.inner.calls.load(Ordering::SeqCst), 1);
+    assert!(dir.path().join("SOL.html").exists());
+
+    let stale = CachedSource::new(cached.inner, dir.path(), Duration::ZERO);
+    assert_eq!(stale.fetch(&request).await.unwrap().body, "fresh page");
+    assert_eq!(stale.inner.revalidations.load(Ordering::SeqCst), 1);
+    assert_eq!(stale.inner.calls.load(Ordering::SeqCst), 1);
+
+    stale.inner.down.store(true, Ordering::SeqCst);
+    assert_eq!(stale.fetch(&request).await.unwrap().body, "fresh page");
+    assert_eq!(stale.inner.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_subsecond_ttl() {
+    use std::sync::atomic::Ordering;
+    let dir = tempfile::tempdir().unwrap();
+    let request = PageRequest::character(crate::CharacterId::SOL, "https://www.dustloop.com/w/GGST/Sol_Badguy/Frame_Data");
+    let inner = CountingSource { calls: Default::default(), revalidations: Default::default(), down: Default::default() };
+
+    let cached = CachedSource::new(inner, dir.path(), Duration::from_millis(300));
+    cached.fetch(&request).await.unwrap();
+    cached.fetch(&request).await.unwrap();
+    assert_eq!(cached.inner.revalidations.load(Ordering::SeqCst), 0);
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    cached.fetch(&request).await.unwrap();
+    assert_eq!(cached.inner.revalidations.load(Ordering::SeqCst), 1);
+
+    let metadata = Metadata { fetched_at: Duration::from_millis(1500), ..Metadata::default() };
+    assert_eq!(Metadata::parse(&metadata.to_string()), metadata);
+}
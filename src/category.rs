@@ -0,0 +1,64 @@
+/// What kind of move a row is, taken from the heading of the table it was found in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveCategory {
+    Normal,
+    /// A normal with a direction other than neutral or down, e.g. `6P`
+    CommandNormal,
+    Special,
+    /// Overdrives (supers)
+    Overdrive,
+    Throw,
+    /// System mechanics (Roman Cancels, Wild Assault, Deflect Shield...) and anything under an unknown heading
+    #[default]
+    System,
+}
+
+impl MoveCategory {
+    /// The category of the moves under a section heading, e.g. `Special Moves`. `None` when the heading is not known.
+    pub fn from_heading(heading: &str) -> Option<MoveCategory> {
+        let heading = heading.to_lowercase();
+        let category = match heading.as_str() {
+            h if h.contains("throw") => MoveCategory::Throw,
+            h if h.contains("overdrive") || h.contains("super") => MoveCategory::Overdrive,
+            h if h.contains("special") => MoveCategory::Special,
+            h if h.contains("command normal") || h.contains("unique") => MoveCategory::CommandNormal,
+            h if h.contains("normal") => MoveCategory::Normal,
+            h if ["system", "other", "mechanic", "roman cancel", "wild assault", "deflect", "burst"].iter().any(|k| h.contains(k)) => {
+                MoveCategory::System
+            },
+            _ => return None,
+        };
+        Some(category)
+    }
+
+    /// Narrows the category of a section down for a single move: directional normals are command normals and
+    /// throws listed among normals or system moves are throws
+    pub(crate) fn refine(self, input: &str, name: &str) -> MoveCategory {
+        match self {
+            MoveCategory::Normal | MoveCategory::System if name.to_lowercase().contains("throw") => MoveCategory::Throw,
+            MoveCategory::Normal => {
+                let direction = input.trim_start_matches(|c: char| c.is_ascii_alphabetic() || c == '.').chars().next();
+                if matches!(direction, Some('1' | '3' | '4' | '6' | '7' | '9')) {
+                    MoveCategory::CommandNormal
+                } else {
+                    MoveCategory::Normal
+                }
+            },
+            category => category,
+        }
+    }
+}
+
+#[test]
+fn categories() {
+    assert_eq!(MoveCategory::from_heading("Normal Moves"), Some(MoveCategory::Normal));
+    assert_eq!(MoveCategory::from_heading("Overdrives"), Some(MoveCategory::Overdrive));
+    assert_eq!(MoveCategory::from_heading("Throws"), Some(MoveCategory::Throw));
+    assert_eq!(MoveCategory::from_heading("Wild Assault"), Some(MoveCategory::System));
+    assert_eq!(MoveCategory::from_heading("Gallery"), None);
+    assert_eq!(MoveCategory::Normal.refine("6P", "6P"), MoveCategory::CommandNormal);
+    assert_eq!(MoveCategory::Normal.refine("j.H", "j.H"), MoveCategory::Normal);
+    assert_eq!(MoveCategory::Normal.refine("j.6H", "j.6H"), MoveCategory::CommandNormal);
+    assert_eq!(MoveCategory::System.refine("6D", "Ground Throw"), MoveCategory::Throw);
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// The display text of a table cell, with the notes pulled out of it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CellText {
+    /// Line breaks are kept as `\n`, everything else is collapsed to single spaces
+    pub text: String,
+    /// Tooltips and footnotes, in the order they appear
+    pub notes: Vec<String>,
+}
+
+fn get_footnote_selector() -> &'static Selector {
+    static FOOTNOTE_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    FOOTNOTE_SELECTOR.get_or_init(|| Selector::parse(r#"li[id^="cite_note"]"#).unwrap())
+}
+
+fn get_footnote_text_selector() -> &'static Selector {
+    static FOOTNOTE_TEXT_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    FOOTNOTE_TEXT_SELECTOR.get_or_init(|| Selector::parse(".reference-text").unwrap())
+}
+
+fn get_link_selector() -> &'static Selector {
+    static LINK_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    LINK_SELECTOR.get_or_init(|| Selector::parse("a[href]").unwrap())
+}
+
+/// The text of every footnote on the page by its id (`cite_note-1`), so markers in cells can be resolved
+pub(crate) fn footnotes(document: &Html) -> HashMap<String, String> {
+    document.select(get_footnote_selector())
+        .filter_map(|note| {
+            let id = note.value().attr("id")?;
+            let content = note.select(get_footnote_text_selector()).next().unwrap_or(note);
+            Some((id.to_string(), collapse(&content.text().collect::<String>())))
+        })
+        .collect()
+}
+
+/// Turns a cell into display text: entities decoded, tags dropped, `<br>` and block elements as line breaks,
+/// tooltips and footnote markers moved into the notes
+pub(crate) fn clean(cell: ElementRef, footnotes: &HashMap<String, String>) -> CellText {
+    let mut raw = String::new();
+    let mut notes = vec![];
+    walk(cell, &mut raw, &mut notes, footnotes);
+    let text = raw.lines().map(collapse).filter(|l| !l.is_empty()).collect::<Vec<String>>().join("\n");
+    let mut unique: Vec<String> = vec![];
+    for note in notes.into_iter().filter(|n| !n.is_empty()) {
+        if !unique.contains(&note) {
+            unique.push(note);
+        }
+    }
+    CellText { text, notes: unique }
+}
+
+fn walk(element: ElementRef, out: &mut String, notes: &mut Vec<String>, footnotes: &HashMap<String, String>) {
+    for child in element.children() {
+        match child.value() {
+            // line breaks in the source are just whitespace, only markup breaks lines
+            Node::Text(text) => out.extend(text.chars().map(|c| if c.is_whitespace() { ' ' } else { c })),
+            Node::Element(_) => {
+                let Some(child) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                let value = child.value();
+                match value.name() {
+                    "br" => out.push('\n'),
+                    "script" | "style" => {},
+                    "sup" if value.classes().any(|c| c == "reference") => {
+                        let target = child.select(get_link_selector()).next()
+                            .and_then(|a| a.value().attr("href"))
+                            .and_then(|href| href.split_once('#'))
+                            .and_then(|(_, id)| footnotes.get(id));
+                        notes.push(target.cloned().unwrap_or_else(|| collapse(&child.text().collect::<String>())));
+                    },
+                    _ if value.classes().any(|c| c == "tooltiptext") => {
+                        notes.push(collapse(&child.text().collect::<String>()));
+                    },
+                    name => {
+                        // links carry the page name as their title, anything else with one is a hover note
+                        if let Some(title) = value.attr("title").filter(|_| name != "a") {
+                            notes.push(collapse(title));
+                        }
+                        let block = matches!(name, "p" | "div" | "li" | "ul" | "ol");
+                        if block {
+                            out.push('\n');
+                        }
+                        walk(child, out, notes, footnotes);
+                        if block {
+                            out.push('\n');
+                        }
+                    },
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[test]
+fn clean_cells() {
+    let html = Html::parse_fragment(r##"<table><tr><td> 40<br>50 &amp; more<sup class="reference"><a href="#cite_note-1">[1]</a></sup>
+        <span class="tooltip">+1<span class="tooltiptext">&quot;+3&quot; on the second hit</span></span></td></tr></table>"##);
+    let cell = html.select(&Selector::parse("td").unwrap()).next().unwrap();
+    let footnotes = HashMap::from([(String::from("cite_note-1"), String::from("Only when charged"))]);
+    let cleaned = clean(cell, &footnotes);
+    assert_eq!(cleaned.text, "40\n50 & more +1");
+    assert_eq!(cleaned.notes, vec!["Only when charged", "\"+3\" on the second hit"]);
+}
@@ -0,0 +1,138 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use scraper::Selector;
+
+use crate::{CharacterId, GGSTDLError, PageRequest, PageSource, Roster, RosterEntry};
+
+/// The Dustloop GGST landing page, which links to every character
+pub const INDEX_URL: &str = "https://www.dustloop.com/w/GGST";
+
+/// A character with a frame data page on the wiki
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredCharacter {
+    /// The name as used in the page url, e.g. `Sol Badguy`
+    pub name: String,
+    pub frame_data_url: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Discovery {
+    /// Every character with a frame data page, in the order they are linked
+    pub characters: Vec<DiscoveredCharacter>,
+    /// The characters that are not in the roster discovery was run against
+    pub missing: Vec<DiscoveredCharacter>,
+}
+
+fn get_link_selector() -> &'static Selector {
+    static LINK_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    LINK_SELECTOR.get_or_init(|| Selector::parse("a[href]").unwrap())
+}
+
+fn get_frame_data_link_matcher() -> &'static Regex {
+    static FRAME_DATA_LINK_MATCHER: OnceLock<Regex> = OnceLock::new();
+    FRAME_DATA_LINK_MATCHER.get_or_init(|| Regex::new(r"^(?:https?://(?:www\.)?dustloop\.com)?/w/GGST/([^/#?]+)/Frame_Data/?$").unwrap())
+}
+
+fn get_page_name_matcher() -> &'static Regex {
+    static PAGE_NAME_MATCHER: OnceLock<Regex> = OnceLock::new();
+    PAGE_NAME_MATCHER.get_or_init(|| Regex::new(r"/w/GGST/([^/#?]+)").unwrap())
+}
+
+impl DiscoveredCharacter {
+    /// A roster entry for this character, found by its name
+    pub fn to_roster_entry(&self) -> RosterEntry {
+        let id = CharacterId::parse(&simplify(&self.name));
+        RosterEntry::new(id, self.name.clone(), format!("(?i)({})", regex::escape(&self.name)), self.frame_data_url.clone())
+    }
+}
+
+impl Discovery {
+    /// A roster of just the missing characters, to merge into the roster discovery was run against
+    pub fn missing_roster(&self) -> Roster {
+        Roster { entries: self.missing.iter().map(DiscoveredCharacter::to_roster_entry).collect() }
+    }
+}
+
+/// Fetches the GGST index page from `source` and finds every character frame data page it links to
+pub async fn discover(source: &dyn PageSource, roster: &Roster) -> Result<Discovery, GGSTDLError> {
+    let request = PageRequest::url(INDEX_URL);
+    let page = source.fetch(&request).await?;
+    if !page.is_success() {
+        return Err(GGSTDLError::Http { url: request.url, status: page.status.unwrap_or_default() });
+    }
+    Ok(discover_from_html(&page.body, roster))
+}
+
+/// Finds every character frame data page linked from an already fetched GGST index page
+pub fn discover_from_html(html: &str, roster: &Roster) -> Discovery {
+    let document = scraper::Html::parse_document(html);
+    let mut discovery = Discovery::default();
+    for link in document.select(get_link_selector()) {
+        let Some(href) = link.value().attr("href") else {
+            continue;
+        };
+        let Some(captures) = get_frame_data_link_matcher().captures(href) else {
+            continue;
+        };
+        let name = percent_decode(&captures[1]).replace('_', " ");
+        if discovery.characters.iter().any(|c| c.name == name) {
+            continue;
+        }
+        discovery.characters.push(DiscoveredCharacter {
+            frame_data_url: format!("{}/{}/Frame_Data", INDEX_URL, &captures[1]),
+            name,
+        });
+    }
+
+    let known = roster.entries.iter()
+        .flat_map(|e| {
+            let page = get_page_name_matcher().captures(&e.frame_data_url).map(|c| simplify(&percent_decode(&c[1])));
+            [Some(simplify(&e.name)), page]
+        })
+        .flatten()
+        .collect::<Vec<String>>();
+    discovery.missing = discovery.characters.iter()
+        .filter(|c| !known.contains(&simplify(&c.name)))
+        .cloned()
+        .collect();
+    discovery
+}
+
+fn simplify(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_uppercase()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut ind = 0;
+    while ind < bytes.len() {
+        let hex = bytes.get(ind + 1..ind + 3).and_then(|h| std::str::from_utf8(h).ok()).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[ind], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                ind += 3;
+            },
+            (byte, _) => {
+                decoded.push(byte);
+                ind += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[tokio::test]
+async fn discover_from_fixture() {
+    let source = crate::DirSource::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"));
+    let discovery = discover(&source, &Roster::builtin()).await.unwrap();
+    assert_eq!(discovery.characters.len(), 31);
+    assert!(discovery.characters.iter().any(|c| c.name == "Bedman?"));
+    let missing = discovery.missing.iter().map(|c| c.name.as_str()).collect::<Vec<&str>>();
+    assert_eq!(missing, vec!["Slayer", "Dizzy", "Venom", "Unika"]);
+
+    let roster = discovery.missing_roster();
+    assert_eq!(roster.entries[0].id, CharacterId::Other(String::from("SLAYER")));
+    assert_eq!(roster.entries[0].frame_data_url, "https://www.dustloop.com/w/GGST/Slayer/Frame_Data");
+}
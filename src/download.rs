@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::{Character, GGSTDLData, GGSTDLError, Hitbox};
+
+/// Downloads hitbox images into a local directory so they only have to be fetched from the wiki once.
+///
+/// Images are stored under the SHA-256 of their contents (`<hash>.png`), so the same image linked from several moves
+/// is kept once. An `index` file in the directory maps every downloaded url to its hash, images already in the index
+/// are not requested again.
+#[derive(Debug, Clone)]
+pub struct HitboxStore {
+    path: PathBuf,
+    client: reqwest::Client,
+}
+
+/// What happened while downloading hitbox images
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DownloadReport {
+    /// Images fetched from the wiki
+    pub downloaded: usize,
+    /// Images that were already in the store
+    pub reused: usize,
+    /// Images that could not be fetched or written, their hitboxes keep no local path
+    pub failed: Vec<GGSTDLError>,
+}
+
+impl HitboxStore {
+    pub fn new(path: impl AsRef<Path>) -> HitboxStore {
+        HitboxStore::with_client(path, reqwest::Client::new())
+    }
+
+    pub fn with_client(path: impl AsRef<Path>, client: reqwest::Client) -> HitboxStore {
+        HitboxStore { path: path.as_ref().to_path_buf(), client }
+    }
+
+    /// Downloads the hitboxes of every character and points them at their local copies
+    pub async fn download_all(&self, data: &mut GGSTDLData) -> Result<DownloadReport, GGSTDLError> {
+        let mut index = self.read_index().await?;
+        let mut report = DownloadReport::default();
+        for character in &mut data.characters {
+            self.download_into(character, &mut index, &mut report).await;
+        }
+        self.write_index(&index).await?;
+        Ok(report)
+    }
+
+    /// Downloads the hitboxes of a single character and points them at their local copies
+    pub async fn download_character(&self, character: &mut Character) -> Result<DownloadReport, GGSTDLError> {
+        let mut index = self.read_index().await?;
+        let mut report = DownloadReport::default();
+        self.download_into(character, &mut index, &mut report).await;
+        self.write_index(&index).await?;
+        Ok(report)
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.path.join("index")
+    }
+
+    fn object_path(&self, hash: &str, hitbox: &Hitbox) -> PathBuf {
+        let extension = hitbox.file_name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("png");
+        self.path.join(format!("{}.{}", hash, extension))
+    }
+
+    async fn download_into(&self, character: &mut Character, index: &mut BTreeMap<String, String>, report: &mut DownloadReport) {
+        for hitbox in character.moves.iter_mut().flat_map(|m| m.hitboxes.iter_mut()) {
+            if let Some(hash) = index.get(&hitbox.url) {
+                let path = self.object_path(hash, hitbox);
+                if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                    hitbox.local_path = Some(path);
+                    report.reused += 1;
+                    continue;
+                }
+            }
+            match self.download(hitbox).await {
+                Ok((hash, path)) => {
+                    index.insert(hitbox.url.clone(), hash);
+                    hitbox.local_path = Some(path);
+                    report.downloaded += 1;
+                },
+                Err(e) => report.failed.push(e),
+            }
+        }
+    }
+
+    async fn download(&self, hitbox: &Hitbox) -> Result<(String, PathBuf), GGSTDLError> {
+        let fetch_error = |e: reqwest::Error| GGSTDLError::Fetch { url: hitbox.url.clone(), message: e.to_string() };
+        let res = self.client.get(hitbox.url.as_str()).send().await.map_err(fetch_error)?;
+        if !res.status().is_success() {
+            return Err(GGSTDLError::Http { url: hitbox.url.clone(), status: res.status().as_u16() });
+        }
+        let bytes = res.bytes().await.map_err(fetch_error)?;
+        let hash = Sha256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        let path = self.object_path(&hash, hitbox);
+        // same hash, same contents, an existing file is already right
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            tokio::fs::create_dir_all(&self.path).await.map_err(|e| io_error(&self.path, e))?;
+            tokio::fs::write(&path, &bytes).await.map_err(|e| io_error(&path, e))?;
+        }
+        Ok((hash, path))
+    }
+
+    async fn read_index(&self) -> Result<BTreeMap<String, String>, GGSTDLError> {
+        let path = self.index_path();
+        let text = match tokio::fs::read_to_string(&path).await {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(io_error(&path, e)),
+        };
+        // one `<hash> <url>` per line
+        Ok(text.lines()
+            .filter_map(|l| l.split_once(' '))
+            .map(|(hash, url)| (url.to_string(), hash.to_string()))
+            .collect())
+    }
+
+    async fn write_index(&self, index: &BTreeMap<String, String>) -> Result<(), GGSTDLError> {
+        let text = index.iter().map(|(url, hash)| format!("{} {}\n", hash, url)).collect::<String>();
+        tokio::fs::create_dir_all(&self.path).await.map_err(|e| io_error(&self.path, e))?;
+        let path = self.index_path();
+        tokio::fs::write(&path, text).await.map_err(|e| io_error(&path, e))
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> GGSTDLError {
+    GGSTDLError::Io { path: path.display().to_string(), message: e.to_string() }
+}
+
+#[tokio::test]
+async fn download_hitboxes_from_mock_server() {
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mut load = crate::load_sol_fixture().await;
+    let server = MockServer::start().await;
+    let sol = load.characters.iter_mut().find(|c| c.id == crate::CharacterId::SOL).unwrap();
+    for hitbox in sol.moves.iter_mut().flat_map(|m| m.hitboxes.iter_mut()) {
+        hitbox.url = format!("{}/images/{}", server.uri(), hitbox.file_name);
+    }
+    let images = sol.moves.iter().map(|m| m.hitboxes.len()).sum::<usize>();
+    Mock::given(method("GET")).and(path_regex(r"Hitbox2?\.png$"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"not really a png".to_vec()))
+        .expect(images as u64)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = HitboxStore::new(dir.path());
+    let report = store.download_all(&mut load).await.unwrap();
+    assert_eq!(report, DownloadReport { downloaded: images, reused: 0, failed: vec![] });
+    let sol = load.find_character("sol").unwrap();
+    assert!(sol.moves.iter().flat_map(|m| &m.hitboxes).all(|h| h.local_path.as_ref().is_some_and(|p| p.exists())));
+    // every image has the same contents, so the store holds it once next to the index
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+
+    // a second run is served from the index without touching the server
+    let sol = load.characters.iter_mut().find(|c| c.id == crate::CharacterId::SOL).unwrap();
+    let report = store.download_character(sol).await.unwrap();
+    assert_eq!(report.reused, images);
+}
@@ -0,0 +1,57 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::CharacterId;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GGSTDLError {
+    UnknownCharacter { query: String },
+    UnknownMove { character: CharacterId, query: String },
+    /// The query matched several entries equally well
+    AmbiguousQuery { query: String, candidates: Vec<String> },
+    /// The page could not be fetched at all, e.g. a connection failure
+    Fetch { url: String, message: String },
+    /// The server answered with an unsuccessful status
+    Http { url: String, status: u16 },
+    /// Reading or writing a local file failed
+    Io { path: String, message: String },
+    /// A frame data section was not found on the character's page
+    MissingSection { character: CharacterId, section: String },
+    /// A table row could not be turned into a move, `column` is the index of the offending cell when there is one
+    MalformedRow { character: CharacterId, section: String, row: usize, column: Option<usize>, reason: String },
+    InvalidAliasRegex { pattern: String, message: String },
+    /// Loading the character stopped before it finished, e.g. because the task panicked
+    LoadFailed { character: CharacterId, message: String },
+    /// A JSON document could not be read or written
+    InvalidJson { message: String },
+    /// A TOML document could not be read
+    InvalidToml { message: String },
+    /// The JSON document was written by an incompatible version of the format
+    UnsupportedJsonVersion { version: u32 },
+}
+
+impl Display for GGSTDLError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GGSTDLError::UnknownCharacter { query } => write!(f, "Unknown character '{}'", query),
+            GGSTDLError::UnknownMove { character, query } => write!(f, "Unknown move '{}' for {}", query, character),
+            GGSTDLError::AmbiguousQuery { query, candidates } => write!(f, "Ambiguous query '{}', could be any of: {}", query, candidates.join(", ")),
+            GGSTDLError::Fetch { url, message } => write!(f, "Error fetching {}: {}", url, message),
+            GGSTDLError::Http { url, status } => write!(f, "HTTP {} from {}", status, url),
+            GGSTDLError::Io { path, message } => write!(f, "Error accessing {}: {}", path, message),
+            GGSTDLError::MissingSection { character, section } => write!(f, "Could not find section {} for {}", section, character),
+            GGSTDLError::MalformedRow { character, section, row, column, reason } => match column {
+                Some(column) => write!(f, "Malformed row {} (column {}) in section {} for {}: {}", row, column, section, character, reason),
+                None => write!(f, "Malformed row {} in section {} for {}: {}", row, section, character, reason),
+            },
+            GGSTDLError::InvalidAliasRegex { pattern, message } => write!(f, "Invalid alias regex '{}': {}", pattern, message),
+            GGSTDLError::LoadFailed { character, message } => write!(f, "Loading {} failed: {}", character, message),
+            GGSTDLError::InvalidJson { message } => write!(f, "Invalid JSON: {}", message),
+            GGSTDLError::InvalidToml { message } => write!(f, "Invalid TOML: {}", message),
+            GGSTDLError::UnsupportedJsonVersion { version } => write!(f, "Unsupported JSON version {}", version),
+        }
+    }
+}
+
+impl Error for GGSTDLError {}
@@ -0,0 +1,253 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// A parsed startup, active or recovery cell from a frame data table
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameValue {
+    /// Nothing listed, either an empty cell or a lone `-`
+    #[default]
+    Empty,
+    /// A single frame count, e.g. `7`
+    Single(i32),
+    /// An inclusive range of frame counts, e.g. `5~9`
+    Range(i32, i32),
+    /// Several values in sequence, e.g. the hits of `3,3,3` or `3*3`, the active and inactive frames of `3(5)3`, or a
+    /// value and its alternate in `20 (24)` or `7 or 10`
+    Segments(Vec<FrameSegment>),
+    /// A worded value such as `Total 45` or `Until Landing`
+    Special(SpecialFrames),
+    /// Anything the parser does not understand, kept as the original text
+    Unknown(String),
+}
+
+/// One part of a [`FrameValue::Segments`] value
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameSegment {
+    pub value: FrameValue,
+    /// Dustloop puts gaps between active frames and alternate values in parentheses or brackets
+    pub parenthesised: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpecialFrames {
+    /// `Total 45` or `45 total`
+    Total(i32),
+    /// `Until Landing`
+    UntilLanding,
+    /// `8 after landing` or `Landing 8`
+    Landing(i32),
+}
+
+pub(crate) fn get_tag_matcher() -> &'static Regex {
+    static TAG_MATCHER: OnceLock<Regex> = OnceLock::new();
+    TAG_MATCHER.get_or_init(|| Regex::new(r"<[^>]*>").unwrap())
+}
+
+fn get_range_matcher() -> &'static Regex {
+    static RANGE_MATCHER: OnceLock<Regex> = OnceLock::new();
+    RANGE_MATCHER.get_or_init(|| Regex::new(r"^(\d+)\s*[~～-]\s*(\d+)$").unwrap())
+}
+
+fn get_repeat_matcher() -> &'static Regex {
+    static REPEAT_MATCHER: OnceLock<Regex> = OnceLock::new();
+    // `3*3` is 3 hits of 3 active frames each
+    REPEAT_MATCHER.get_or_init(|| Regex::new(r"^(\d+)\s*[*×]\s*(\d+)$").unwrap())
+}
+
+fn get_alternate_matcher() -> &'static Regex {
+    static ALTERNATE_MATCHER: OnceLock<Regex> = OnceLock::new();
+    // `7 or 10` is written like `7 (10)` elsewhere
+    ALTERNATE_MATCHER.get_or_init(|| Regex::new(r"(?i)\s+or\s+(\d+(?:\s*[~～-]\s*\d+)?)").unwrap())
+}
+
+fn get_total_matcher() -> &'static Regex {
+    static TOTAL_MATCHER: OnceLock<Regex> = OnceLock::new();
+    TOTAL_MATCHER.get_or_init(|| Regex::new(r"(?i)^(?:total\s*(\d+)|(\d+)\s*total)$").unwrap())
+}
+
+fn get_landing_matcher() -> &'static Regex {
+    static LANDING_MATCHER: OnceLock<Regex> = OnceLock::new();
+    LANDING_MATCHER.get_or_init(|| Regex::new(r"(?i)^(?:(\d+)\s*(?:after|upon)\s*landing|landing\s*(\d+))$").unwrap())
+}
+
+fn get_until_landing_matcher() -> &'static Regex {
+    static UNTIL_LANDING_MATCHER: OnceLock<Regex> = OnceLock::new();
+    UNTIL_LANDING_MATCHER.get_or_init(|| Regex::new(r"(?i)^until\s*landing$").unwrap())
+}
+
+impl FrameValue {
+    /// Parses a cell as scraped from Dustloop. Any markup in the cell is ignored.
+    ///
+    /// Understands numbers, ranges (`5~9`, `2-4`), repeated hits (`3*3`), sequences and alternates (`3,3`, `3(5)3`,
+    /// `20 [24]`, `7 or 10`), `Total 45` and the landing forms. The formats were collected from the saved Sol page and
+    /// the forms above seen on other characters, not checked against every character's page, so other wordings
+    /// (e.g. notes like `See below`) end up as [`FrameValue::Unknown`].
+    pub fn parse(raw: &str) -> FrameValue {
+        let text = get_tag_matcher().replace_all(raw, "");
+        let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+        let alternates = get_alternate_matcher().replace_all(&text, "($1)");
+        if text.is_empty() || text == "-" {
+            return FrameValue::Empty;
+        }
+
+        let Some(segments) = split_segments(&alternates) else {
+            return FrameValue::Unknown(text);
+        };
+        let mut parsed = vec![];
+        for (segment, parenthesised) in segments {
+            if let Some((frames, hits)) = parse_repeat(segment) {
+                parsed.extend((0..hits).map(|_| FrameSegment { value: FrameValue::Single(frames), parenthesised }));
+                continue;
+            }
+            let Some(value) = parse_atom(segment) else {
+                return FrameValue::Unknown(text);
+            };
+            parsed.push(FrameSegment { value, parenthesised });
+        }
+
+        match parsed.len() {
+            0 => FrameValue::Empty,
+            1 if !parsed[0].parenthesised => parsed.remove(0).value,
+            _ => FrameValue::Segments(parsed),
+        }
+    }
+
+    /// The smallest frame count mentioned anywhere in the value
+    pub fn min(&self) -> Option<i32> {
+        self.numbers().into_iter().min()
+    }
+
+    /// The largest frame count mentioned anywhere in the value
+    pub fn max(&self) -> Option<i32> {
+        self.numbers().into_iter().max()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, FrameValue::Empty)
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, FrameValue::Unknown(_))
+    }
+
+    fn numbers(&self) -> Vec<i32> {
+        match self {
+            FrameValue::Single(v) => vec![*v],
+            FrameValue::Range(low, high) => vec![*low, *high],
+            FrameValue::Segments(segments) => segments.iter().flat_map(|s| s.value.numbers()).collect(),
+            FrameValue::Special(SpecialFrames::Total(v)) | FrameValue::Special(SpecialFrames::Landing(v)) => vec![*v],
+            _ => vec![],
+        }
+    }
+}
+
+// splits on top level ',' and '+' and pulls out anything in parentheses or brackets, None if they are unbalanced
+fn split_segments(text: &str) -> Option<Vec<(&str, bool)>> {
+    let mut segments = vec![];
+    let mut start = 0;
+    let mut open: Option<(char, usize)> = None;
+    for (ind, c) in text.char_indices() {
+        match (open, c) {
+            (None, '(' | '[') => {
+                segments.push((&text[start..ind], false));
+                open = Some((if c == '(' { ')' } else { ']' }, ind + 1));
+            },
+            (None, ')' | ']') => return None,
+            (None, ',' | '+') => {
+                segments.push((&text[start..ind], false));
+                start = ind + 1;
+            },
+            (Some((close, from)), _) if c == close => {
+                segments.push((&text[from..ind], true));
+                open = None;
+                start = ind + 1;
+            },
+            _ => {}
+        }
+    }
+    if open.is_some() {
+        return None;
+    }
+    segments.push((&text[start..], false));
+    Some(segments.into_iter().map(|(s, p)| (s.trim(), p)).filter(|(s, _)| !s.is_empty()).collect())
+}
+
+// frames and number of hits of `3*3`, only for a believable number of hits
+fn parse_repeat(atom: &str) -> Option<(i32, usize)> {
+    let captures = get_repeat_matcher().captures(atom)?;
+    let hits = captures[2].parse::<usize>().ok().filter(|hits| (1..=99).contains(hits))?;
+    Some((captures[1].parse().ok()?, hits))
+}
+
+fn parse_atom(atom: &str) -> Option<FrameValue> {
+    if let Ok(value) = atom.parse::<i32>() {
+        return Some(FrameValue::Single(value));
+    }
+    if let Some(captures) = get_range_matcher().captures(atom) {
+        return Some(FrameValue::Range(captures[1].parse().ok()?, captures[2].parse().ok()?));
+    }
+    if let Some(captures) = get_total_matcher().captures(atom) {
+        let value = captures.get(1).or(captures.get(2))?.as_str().parse().ok()?;
+        return Some(FrameValue::Special(SpecialFrames::Total(value)));
+    }
+    if let Some(captures) = get_landing_matcher().captures(atom) {
+        let value = captures.get(1).or(captures.get(2))?.as_str().parse().ok()?;
+        return Some(FrameValue::Special(SpecialFrames::Landing(value)));
+    }
+    if get_until_landing_matcher().is_match(atom) {
+        return Some(FrameValue::Special(SpecialFrames::UntilLanding));
+    }
+    None
+}
+
+#[test]
+fn parse_simple_values() {
+    assert_eq!(FrameValue::parse(""), FrameValue::Empty);
+    assert_eq!(FrameValue::parse(" - "), FrameValue::Empty);
+    assert_eq!(FrameValue::parse("7"), FrameValue::Single(7));
+    assert_eq!(FrameValue::parse("5~9"), FrameValue::Range(5, 9));
+    assert_eq!(FrameValue::parse("Total 45"), FrameValue::Special(SpecialFrames::Total(45)));
+    assert_eq!(FrameValue::parse("Until Landing"), FrameValue::Special(SpecialFrames::UntilLanding));
+    assert_eq!(FrameValue::parse("12 after landing"), FrameValue::Special(SpecialFrames::Landing(12)));
+    assert_eq!(FrameValue::parse("<span>13</span>"), FrameValue::Single(13));
+    assert_eq!(FrameValue::parse("lots of frames"), FrameValue::Unknown(String::from("lots of frames")));
+}
+
+#[test]
+fn parse_segmented_values() {
+    let value = FrameValue::parse("3(5)3");
+    assert_eq!(value, FrameValue::Segments(vec![
+        FrameSegment { value: FrameValue::Single(3), parenthesised: false },
+        FrameSegment { value: FrameValue::Single(5), parenthesised: true },
+        FrameSegment { value: FrameValue::Single(3), parenthesised: false },
+    ]));
+    assert_eq!(value.min(), Some(3));
+    assert_eq!(value.max(), Some(5));
+
+    let value = FrameValue::parse("Until Landing+8");
+    assert_eq!(value, FrameValue::Segments(vec![
+        FrameSegment { value: FrameValue::Special(SpecialFrames::UntilLanding), parenthesised: false },
+        FrameSegment { value: FrameValue::Single(8), parenthesised: false },
+    ]));
+
+    assert_eq!(FrameValue::parse("2,2,2").min(), Some(2));
+    assert_eq!(FrameValue::parse("20 [24~26]").max(), Some(26));
+    assert!(FrameValue::parse("3(5").is_unknown());
+}
+
+#[test]
+fn parse_other_character_forms() {
+    assert_eq!(FrameValue::parse("2-4"), FrameValue::Range(2, 4));
+    let single = |v| FrameSegment { value: FrameValue::Single(v), parenthesised: false };
+    assert_eq!(FrameValue::parse("3*3"), FrameValue::Segments(vec![single(3), single(3), single(3)]));
+    assert_eq!(FrameValue::parse("7 or 10"), FrameValue::Segments(vec![
+        single(7),
+        FrameSegment { value: FrameValue::Single(10), parenthesised: true },
+    ]));
+    assert_eq!(FrameValue::parse("5~7 or 12").max(), Some(12));
+    assert!(FrameValue::parse("3*1000").is_unknown());
+}
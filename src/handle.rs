@@ -0,0 +1,185 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant};
+
+use crate::{GGSTDLData, GGSTDLError, MoveAliases, PageSource, Roster};
+
+/// Shared access to frame data that can be refreshed while it is being read.
+///
+/// Readers get the current [`GGSTDLData`] without locking, a refresh swaps in the new data atomically once it has
+/// fully loaded. User aliases are kept next to the data, so changing them does not copy it and refreshes keep them.
+/// Cloning the handle is cheap and every clone sees the same data and aliases.
+#[derive(Clone)]
+pub struct GGSTDLHandle {
+    data: Arc<ArcSwap<GGSTDLData>>,
+    source: Arc<dyn PageSource>,
+    roster: Arc<Roster>,
+    aliases: Arc<ArcSwap<MoveAliases>>,
+}
+
+impl GGSTDLHandle {
+    /// Starts from data loaded with the built in roster, refreshes load the built in roster from `source`
+    pub fn new(data: GGSTDLData, source: impl PageSource + 'static) -> GGSTDLHandle {
+        GGSTDLHandle::new_with_roster(data, source, Roster::builtin())
+    }
+
+    /// Starts from already loaded data, refreshes load the characters in `roster` from `source`. Pass the roster the
+    /// data was loaded with, a refresh that loads fewer characters is never swapped in.
+    pub fn new_with_roster(data: GGSTDLData, source: impl PageSource + 'static, roster: Roster) -> GGSTDLHandle {
+        GGSTDLHandle {
+            data: Arc::new(ArcSwap::from_pointee(data)),
+            source: Arc::new(source),
+            roster: Arc::new(roster),
+            aliases: Arc::default(),
+        }
+    }
+
+    /// Loads the built in roster from `source` and keeps using it for refreshes
+    pub async fn load(source: impl PageSource + 'static) -> Result<GGSTDLHandle, GGSTDLError> {
+        GGSTDLHandle::load_with_roster(source, Roster::builtin()).await
+    }
+
+    /// Loads the characters in `roster` from `source` and keeps using both for refreshes
+    pub async fn load_with_roster(source: impl PageSource + 'static, roster: Roster) -> Result<GGSTDLHandle, GGSTDLError> {
+        let source: Arc<dyn PageSource> = Arc::new(source);
+        let data = crate::load_shared(source.clone(), &roster).await?;
+        Ok(GGSTDLHandle {
+            data: Arc::new(ArcSwap::from_pointee(data)),
+            source,
+            roster: Arc::new(roster),
+            aliases: Arc::default(),
+        })
+    }
+
+    /// The data as of now, later refreshes do not affect the returned value
+    pub fn current(&self) -> Arc<GGSTDLData> {
+        self.data.load_full()
+    }
+
+    /// Reloads the data and swaps it in, unless its [`crate::LoadReport`] is worse than the current one.
+    /// Returns whether the data was replaced.
+    pub async fn refresh(&self) -> Result<bool, GGSTDLError> {
+        let data = crate::load_shared(self.source.clone(), &self.roster).await?;
+        if data.report.is_worse_than(&self.data.load().report) {
+            return Ok(false);
+        }
+        self.data.store(Arc::new(data));
+        Ok(true)
+    }
+
+    /// The user aliases as of now, to pass to [`GGSTDLData::find_move_with_aliases`] and friends
+    pub fn aliases(&self) -> Arc<MoveAliases> {
+        self.aliases.load_full()
+    }
+
+    /// Changes the user aliases, e.g. to register a nickname while serving queries. Refreshes do not touch them.
+    /// `update` may be called more than once if another update happens at the same time.
+    pub fn update_aliases(&self, update: impl Fn(&mut MoveAliases)) {
+        self.aliases.rcu(|current| {
+            let mut next = MoveAliases::clone(current);
+            update(&mut next);
+            next
+        });
+    }
+
+    /// Replaces the user aliases, see [`GGSTDLHandle::update_aliases`]
+    pub fn set_aliases(&self, aliases: MoveAliases) {
+        self.aliases.store(Arc::new(aliases));
+    }
+
+    /// Refreshes the data every `period` on a tokio task until the returned handle is aborted.
+    /// Failed refreshes keep the current data and are retried at the next period.
+    pub fn spawn_refresh(&self, period: Duration) -> JoinHandle<()> {
+        let handle = self.clone();
+        tokio::spawn(async move {
+            let mut ticks = interval_at(Instant::now() + period, period);
+            loop {
+                ticks.tick().await;
+                let _ = handle.refresh().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+struct FlakySource {
+    page: &'static str,
+    broken: Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl PageSource for FlakySource {
+    async fn fetch(&self, request: &crate::PageRequest) -> crate::FetchResult {
+        if self.broken.load(std::sync::atomic::Ordering::SeqCst) || request.character != Some(crate::CharacterId::SOL) {
+            return Err(GGSTDLError::Fetch { url: request.url.clone(), message: String::from("unavailable") });
+        }
+        Ok(crate::Page::new(self.page))
+    }
+}
+
+#[tokio::test]
+async fn refresh_keeps_better_data() {
+    let broken = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let source = FlakySource { page: include_str!("../fixtures/SOL.html"), broken: broken.clone() };
+    let handle = GGSTDLHandle::load(source).await.unwrap();
+    let before = handle.current();
+    assert!(handle.refresh().await.unwrap());
+    assert!(!Arc::ptr_eq(&before, &handle.current()));
+
+    broken.store(true, std::sync::atomic::Ordering::SeqCst);
+    let before = handle.current();
+    assert!(!handle.refresh().await.unwrap());
+    assert!(Arc::ptr_eq(&before, &handle.current()));
+    assert!(handle.current().find_move("sol", "5P").is_ok());
+}
+
+#[tokio::test]
+async fn refresh_accepts_removed_moves() {
+    let data = crate::load_sol_fixture().await;
+    // a patch removed Fafnir
+    let patched = include_str!("../fixtures/SOL.html").lines()
+        .filter(|l| !l.contains("<td>41236K</td>"))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let mut source = crate::MemorySource::new();
+    source.insert_character(crate::CharacterId::SOL, patched);
+    let handle = GGSTDLHandle::new(data, source);
+
+    assert!(handle.refresh().await.unwrap());
+    assert!(handle.current().find_move("sol", "fafnir").is_err());
+}
+
+#[tokio::test]
+async fn refresh_with_roster_file_characters() {
+    let slayer = crate::CharacterId::Other(String::from("SLAYER"));
+    let source = || {
+        let mut source = crate::MemorySource::new();
+        source.insert_character(crate::CharacterId::SOL, include_str!("../fixtures/SOL.html"));
+        source.insert_character(slayer.clone(), include_str!("../fixtures/SOL.html"));
+        source
+    };
+    let mut roster = Roster::builtin();
+    roster.entries.push(crate::RosterEntry::new(slayer.clone(), "Slayer", r"(?i)(slayer)", "https://www.dustloop.com/w/GGST/Slayer/Frame_Data"));
+    let data = crate::load_with_roster(source(), &roster).await.unwrap();
+    let handle = GGSTDLHandle::new_with_roster(data, source(), roster);
+    assert!(handle.refresh().await.unwrap());
+    assert!(handle.current().find_move("slayer", "5P").is_ok());
+}
+
+#[tokio::test]
+async fn aliases_survive_refresh() {
+    let broken = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let source = FlakySource { page: include_str!("../fixtures/SOL.html"), broken };
+    let handle = GGSTDLHandle::load(source).await.unwrap();
+    handle.update_aliases(|a| a.add(crate::CharacterId::SOL, "pilebunker", "Fafnir", crate::AliasPriority::Override));
+    assert_eq!(handle.current().find_move_with_aliases("sol", "pilebunker", &handle.aliases()).unwrap().name, "Fafnir");
+
+    assert!(handle.refresh().await.unwrap());
+    assert_eq!(handle.current().find_move_with_aliases("sol", "pilebunker", &handle.aliases()).unwrap().name, "Fafnir");
+    handle.set_aliases(MoveAliases::new());
+    assert!(handle.current().find_move_with_aliases("sol", "pilebunker", &handle.aliases()).is_err());
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+/// A hitbox image of a move
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hitbox {
+    /// The full size image
+    pub url: String,
+    /// The scaled down copy the wiki shows in the table, if that is what the page linked
+    pub thumbnail_url: Option<String>,
+    /// e.g. `GGST_Sol_Badguy_5P_Hitbox.png`
+    pub file_name: String,
+    /// What the image shows, e.g. `Hit 2` or `Charged version`, when the wiki labels it
+    pub caption: Option<String>,
+    /// Position among the move's hitboxes, starting at 0
+    pub order: usize,
+    /// Where the image was saved by [`crate::HitboxStore`], if it was downloaded
+    pub local_path: Option<PathBuf>,
+}
+
+const WIKI_ORIGIN: &str = "https://www.dustloop.com";
+
+fn get_details_selector() -> &'static Selector {
+    static DETAILS_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    DETAILS_SELECTOR.get_or_init(|| Selector::parse("[data-details]").unwrap())
+}
+
+fn get_image_selector() -> &'static Selector {
+    static IMAGE_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    IMAGE_SELECTOR.get_or_init(|| Selector::parse("img[src]").unwrap())
+}
+
+fn get_caption_selector() -> &'static Selector {
+    static CAPTION_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    CAPTION_SELECTOR.get_or_init(|| Selector::parse(".gallerytext, figcaption, .caption").unwrap())
+}
+
+fn get_hitbox_file_matcher() -> &'static Regex {
+    static HITBOX_FILE_MATCHER: OnceLock<Regex> = OnceLock::new();
+    HITBOX_FILE_MATCHER.get_or_init(|| Regex::new(r"(?i)(hitbox|HB)[^/]*\.png$").unwrap())
+}
+
+fn get_thumbnail_matcher() -> &'static Regex {
+    static THUMBNAIL_MATCHER: OnceLock<Regex> = OnceLock::new();
+    // /images/thumb/a/ab/File.png/150px-File.png is a scaled copy of /images/a/ab/File.png
+    THUMBNAIL_MATCHER.get_or_init(|| Regex::new(r"^(.*)/thumb/([^/]+/[^/]+/[^/]+)/[^/]+$").unwrap())
+}
+
+impl Hitbox {
+    /// A hitbox from the `src` of an image on the wiki, resolving relative and thumbnail urls to the full size image
+    pub fn from_src(src: &str, caption: Option<String>, order: usize) -> Hitbox {
+        let absolute = match src {
+            s if s.starts_with("//") => format!("https:{}", s),
+            s if s.starts_with('/') => format!("{}{}", WIKI_ORIGIN, s),
+            s => s.to_string(),
+        };
+        let (url, thumbnail_url) = match get_thumbnail_matcher().captures(&absolute) {
+            Some(captures) => (format!("{}/{}", &captures[1], &captures[2]), Some(absolute.clone())),
+            None => (absolute, None),
+        };
+        let file_name = url.rsplit('/').next().unwrap_or_default().to_string();
+        Hitbox { url, thumbnail_url, file_name, caption, order, local_path: None }
+    }
+}
+
+/// Every hitbox image of a table row, in page order. The images are usually in the hidden details of the row.
+pub(crate) fn row_hitboxes(row: ElementRef) -> Vec<Hitbox> {
+    let details = row.select(get_details_selector())
+        .filter_map(|e| e.value().attr("data-details"))
+        .map(Html::parse_fragment)
+        .collect::<Vec<Html>>();
+    let images = details.iter()
+        .flat_map(|d| d.root_element().select(get_image_selector()).collect::<Vec<ElementRef>>())
+        .chain(row.select(get_image_selector()));
+
+    let mut hitboxes: Vec<Hitbox> = vec![];
+    for image in images {
+        let Some(src) = image.value().attr("src").filter(|src| get_hitbox_file_matcher().is_match(src)) else {
+            continue;
+        };
+        let hitbox = Hitbox::from_src(src, caption(image), hitboxes.len());
+        if !hitboxes.iter().any(|h| h.url == hitbox.url) {
+            hitboxes.push(hitbox);
+        }
+    }
+    hitboxes
+}
+
+// the gallery text next to the image, or its alt text when that says more than the file name
+fn caption(image: ElementRef) -> Option<String> {
+    let gallery_text = image.ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|a| matches!(a.value().name(), "li" | "figure") || a.value().classes().any(|c| c == "gallerybox"))
+        .and_then(|b| b.select(get_caption_selector()).next())
+        .map(|c| c.text().collect::<Vec<&str>>().join(" ").split_whitespace().collect::<Vec<&str>>().join(" "));
+    let alt = image.value().attr("alt").map(str::trim).filter(|alt| !get_hitbox_file_matcher().is_match(alt));
+    gallery_text.or(alt.map(String::from)).filter(|c| !c.is_empty())
+}
+
+#[test]
+fn thumbnail_urls() {
+    let hitbox = Hitbox::from_src("/images/thumb/a/ab/GGST_Sol_Badguy_5P_Hitbox.png/150px-GGST_Sol_Badguy_5P_Hitbox.png", None, 0);
+    assert_eq!(hitbox.url, "https://www.dustloop.com/images/a/ab/GGST_Sol_Badguy_5P_Hitbox.png");
+    assert_eq!(hitbox.thumbnail_url.as_deref(), Some("https://www.dustloop.com/images/thumb/a/ab/GGST_Sol_Badguy_5P_Hitbox.png/150px-GGST_Sol_Badguy_5P_Hitbox.png"));
+    assert_eq!(hitbox.file_name, "GGST_Sol_Badguy_5P_Hitbox.png");
+
+    let hitbox = Hitbox::from_src("https://www.dustloop.com/images/a/ab/GGST_Sol_Badguy_5P_Hitbox.png", None, 0);
+    assert_eq!(hitbox.url, "https://www.dustloop.com/images/a/ab/GGST_Sol_Badguy_5P_Hitbox.png");
+    assert!(hitbox.thumbnail_url.is_none());
+}
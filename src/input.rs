@@ -0,0 +1,213 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A move input in numpad notation, parsed from [`crate::Move::input`], e.g. `j.236K` or `236[H]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Input {
+    /// Stance or range prefix without the dot, e.g. `j`, `c`, `f` or `dj`
+    pub prefix: Option<String>,
+    /// The directions in numpad notation, e.g. `5`, `2`, `236` or `632146`. `None` for inputs like `c.S`.
+    pub motion: Option<String>,
+    /// Whether the first direction is held to charge, written `[4]6`
+    pub charge: bool,
+    pub buttons: Vec<Button>,
+    pub modifier: ButtonModifier,
+    /// Anything after the buttons, e.g. `Level 2` or `~K`
+    pub variant: Option<String>,
+    /// Whether `variant` is written after a space, as in `5H Level 2` but not in `214P~P`
+    pub variant_separated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Button {
+    P, K, S,
+    /// Heavy Slash, written `H` or `HS`
+    H,
+    D,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ButtonModifier {
+    #[default]
+    None,
+    /// The button is held, `[H]`
+    Hold,
+    /// The button is held for part of the charge, `{H}`
+    Partial,
+}
+
+impl Input {
+    /// Reads numpad notation. `None` when the text has neither a direction nor a button, e.g. a move only known by name.
+    pub fn parse(input: &str) -> Option<Input> {
+        let mut rest = input.trim();
+
+        let mut prefix = None;
+        if let Some((head, tail)) = rest.split_once('.') {
+            if (1..=2).contains(&head.len()) && head.chars().all(|c| c.is_ascii_lowercase()) {
+                prefix = Some(head.to_string());
+                rest = tail;
+            }
+        }
+
+        let mut charge = false;
+        let mut motion = String::new();
+        let mut chars = rest.char_indices().peekable();
+        if let (Some('['), Some(direction)) = (rest.chars().next(), rest.chars().nth(1)) {
+            if direction.is_ascii_digit() && rest.chars().nth(2) == Some(']') {
+                charge = true;
+                motion.push(direction);
+                chars.nth(2);
+            }
+        }
+        while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() && *c != '0') {
+            motion.push(c);
+        }
+
+        let mut modifier = ButtonModifier::None;
+        let mut close = None;
+        if let Some((_, open)) = chars.next_if(|(_, c)| *c == '[' || *c == '{') {
+            modifier = if open == '[' { ButtonModifier::Hold } else { ButtonModifier::Partial };
+            close = Some(if open == '[' { ']' } else { '}' });
+        }
+
+        let mut buttons = vec![];
+        let mut previous = None;
+        while let Some((_, c)) = chars.next_if(|(_, c)| "PKSHD+".contains(*c)) {
+            let button = match (previous.replace(c), c) {
+                (Some('H'), 'S') => None, // the S of HS
+                (_, 'P') => Some(Button::P),
+                (_, 'K') => Some(Button::K),
+                (_, 'S') => Some(Button::S),
+                (_, 'H') => Some(Button::H),
+                (_, 'D') => Some(Button::D),
+                _ => None,
+            };
+            buttons.extend(button);
+        }
+        if let Some(close) = close {
+            if chars.next_if(|(_, c)| *c == close).is_none() || buttons.is_empty() {
+                // an unmatched bracket is not a hold, leave it to the variant
+                return Input::parse_plain(input);
+            }
+        }
+
+        // `Dash` or `Kara` are words, not a button followed by a variant
+        let inside_word = chars.peek().is_some_and(|(_, c)| c.is_ascii_lowercase());
+        if motion.is_empty() && (buttons.is_empty() || inside_word) {
+            return None;
+        }
+        // neither are `DI` or `PRC`, a variant is separated from the buttons
+        if chars.peek().is_some_and(|(_, c)| c.is_ascii_uppercase()) {
+            return None;
+        }
+        let variant = chars.peek().map(|(ind, _)| rest[*ind..].trim().to_string()).filter(|v| !v.is_empty());
+        let variant_separated = variant.is_some() && chars.peek().is_some_and(|(_, c)| c.is_whitespace());
+        Some(Input {
+            prefix,
+            motion: Some(motion).filter(|m| !m.is_empty()),
+            charge,
+            buttons,
+            modifier,
+            variant,
+            variant_separated,
+        })
+    }
+
+    // the input without bracket handling, for text like `236[` that only looks like a hold
+    fn parse_plain(input: &str) -> Option<Input> {
+        let cut = input.find(['[', '{']).unwrap_or(input.len());
+        let (head, tail) = input.split_at(cut);
+        let mut parsed = Input::parse(head)?;
+        let gap = &head[head.trim_end().len()..];
+        match parsed.variant.take() {
+            Some(variant) => parsed.variant = Some(format!("{}{}{}", variant, gap, tail).trim_end().to_string()),
+            None => {
+                parsed.variant = Some(tail.trim_end().to_string()).filter(|v| !v.is_empty());
+                parsed.variant_separated = parsed.variant.is_some() && !gap.is_empty();
+            },
+        }
+        Some(parsed)
+    }
+
+    /// Whether the motion is exactly `motion`, e.g. `623` matches `623H` and `j.623K` but not `41236H`
+    pub fn has_motion(&self, motion: &str) -> bool {
+        self.motion.as_deref() == Some(motion)
+    }
+}
+
+impl Display for Button {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(prefix) = &self.prefix {
+            write!(f, "{}.", prefix)?;
+        }
+        if let Some(motion) = &self.motion {
+            match (self.charge, motion.split_at(1)) {
+                (true, (held, release)) => write!(f, "[{}]{}", held, release)?,
+                (false, _) => write!(f, "{}", motion)?,
+            }
+        }
+        // `H+S` and not `HS`, which reads as Heavy Slash
+        let buttons = self.buttons.iter().map(Button::to_string).collect::<Vec<String>>().join("+");
+        match self.modifier {
+            ButtonModifier::None => write!(f, "{}", buttons)?,
+            ButtonModifier::Hold => write!(f, "[{}]", buttons)?,
+            ButtonModifier::Partial => write!(f, "{{{}}}", buttons)?,
+        }
+        if let Some(variant) = &self.variant {
+            let separator = if self.variant_separated { " " } else { "" };
+            write!(f, "{}{}", separator, variant)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn parse_inputs() {
+    let input = Input::parse("j.236K").unwrap();
+    assert_eq!(input.prefix.as_deref(), Some("j"));
+    assert!(input.has_motion("236"));
+    assert_eq!(input.buttons, vec![Button::K]);
+
+    let input = Input::parse("236[H]").unwrap();
+    assert_eq!(input.modifier, ButtonModifier::Hold);
+    assert_eq!(input.buttons, vec![Button::H]);
+    assert_eq!(Input::parse("236{H}").unwrap().modifier, ButtonModifier::Partial);
+
+    let input = Input::parse("c.S").unwrap();
+    assert_eq!((input.prefix.as_deref(), input.motion), (Some("c"), None));
+    assert_eq!(input.buttons, vec![Button::S]);
+
+    let input = Input::parse("5H Level 2").unwrap();
+    assert!(input.has_motion("5"));
+    assert_eq!(input.variant.as_deref(), Some("Level 2"));
+
+    let input = Input::parse("[4]6HS").unwrap();
+    assert!(input.charge && input.has_motion("46"));
+    assert_eq!(input.buttons, vec![Button::H]);
+    assert_eq!(input.to_string(), "[4]6H");
+
+    assert_eq!(Input::parse("632146P").unwrap().motion.as_deref(), Some("632146"));
+    assert_eq!(Input::parse("P+K").unwrap().buttons, vec![Button::P, Button::K]);
+    assert!(Input::parse("Wild Assault").is_none());
+    assert!(Input::parse("Dash").is_none());
+    assert!(Input::parse("DI").is_none());
+    assert!(Input::parse("PRC").is_none());
+
+    let input = Input::parse("H+S").unwrap();
+    assert_eq!(input.to_string(), "H+S");
+    assert_eq!(Input::parse(&input.to_string()).unwrap(), input);
+    assert_eq!(Input::parse("236HS").unwrap().to_string(), "236H");
+    assert_eq!(Input::parse("214P~P").unwrap().to_string(), "214P~P");
+    assert_eq!(Input::parse("c.S~P").unwrap().to_string(), "c.S~P");
+    assert_eq!(Input::parse("5H Level 2").unwrap().to_string(), "5H Level 2");
+    assert_eq!(Input::parse("236[").unwrap().to_string(), "236[");
+}
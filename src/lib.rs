@@ -1,55 +1,99 @@
-use std::{error::Error, fmt, fmt::Display};
+use std::{fmt, fmt::Display};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Formatter;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
 use regex::Regex;
 use tokio::task::JoinSet;
 
+mod advantage;
+mod aliases;
+mod cache;
+mod category;
+mod cell;
+mod discovery;
+mod download;
+mod error;
+mod frames;
+mod handle;
+mod hitbox;
+mod input;
+mod lint;
+mod report;
 mod resolver;
+mod roster;
+mod search;
+#[cfg(feature = "serde")]
+mod serialization;
+mod source;
+mod stats;
+mod translate;
 
-#[derive(Debug)]
-pub enum GGSTDLError {
-    UnknownCharacter, UnknownMove
-}
-
-impl Display for GGSTDLError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            GGSTDLError::UnknownCharacter => write!(f, "Unknown character"),
-            GGSTDLError::UnknownMove => write!(f, "Unknown move"),
-        }
-    }
-}
-
-impl Error for GGSTDLError {}
+pub use advantage::{Advantage, AdvantageEffect};
+pub use aliases::{AliasPriority, MoveAlias, MoveAliases};
+pub use cache::CachedSource;
+pub use category::MoveCategory;
+pub use discovery::{discover, discover_from_html, DiscoveredCharacter, Discovery, INDEX_URL};
+pub use download::{DownloadReport, HitboxStore};
+pub use error::GGSTDLError;
+pub use frames::{FrameSegment, FrameValue, SpecialFrames};
+pub use handle::GGSTDLHandle;
+pub use hitbox::Hitbox;
+pub use input::{Button, ButtonModifier, Input};
+pub use lint::{lint_fixture, AmbiguousQuery, DeadBinding, LintReport, UnreachableMove};
+pub use report::{CharacterReport, LoadReport};
+pub use roster::{Roster, RosterEntry};
+pub use search::{AliasSource, MoveResolution, SCORE_ALIAS, SCORE_EDIT_DISTANCE, SCORE_INPUT, SCORE_NAME, SCORE_SUBSTRING};
+#[cfg(feature = "serde")]
+pub use serialization::JSON_VERSION;
+pub use source::{DirSource, FetchResult, HttpSource, MemorySource, Page, PageKind, PageRequest, PageSource, Revalidated, Validators};
+pub use stats::CharacterStats;
+pub use translate::to_numpad;
 
 #[derive(Debug)]
 pub struct GGSTDLData {
-    pub characters: Vec<Character>
+    pub characters: Vec<Character>,
+    /// What happened while scraping each character, check this to notice characters that silently ended up empty
+    pub report: LoadReport
 }
 
 impl GGSTDLData {
+    /// Finds the character best matching the query. An exact name beats an alias, which beats a partial name,
+    /// and several characters matching equally well is an [`GGSTDLError::AmbiguousQuery`].
     pub fn find_character(&self, char_query: &str) -> Result<&Character, GGSTDLError> {
-        self.characters.iter().find(|c| c.regex.is_match(char_query))
-            .ok_or(GGSTDLError::UnknownCharacter)
+        search::best_character(&self.characters, char_query, |c| search::score_character(c, char_query), |c| &c.id, |c| &c.name)
     }
 
+    /// Finds the first move matching the query. Community motion names are translated first (see [`to_numpad`]),
+    /// so `dp S` finds `623S` even for characters without an alias for it.
     pub fn find_move(&self, char_query: &str, move_query: &str) -> Result<&Move, GGSTDLError> {
-        let character = self.find_character(char_query)?;
-        character.moves.iter().find(|m| m.regex.is_match(move_query))
-            .ok_or(GGSTDLError::UnknownMove)
+        self.resolve_move_explained(char_query, move_query).map(|resolution| resolution.found)
+    }
+
+    /// Finds a move like [`GGSTDLData::find_move`], also trying the user's `aliases` around the built in ones
+    pub fn find_move_with_aliases(&self, char_query: &str, move_query: &str, aliases: &MoveAliases) -> Result<&Move, GGSTDLError> {
+        self.resolve_move_explained_with_aliases(char_query, move_query, aliases).map(|resolution| resolution.found)
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterId {
     TESTAMENT, JACKO, NAGORIYUKI, MILLIA, CHIPP, SOL, KY, MAY, ZATO, INO, HAPPYCHAOS, 
     SIN, BAIKEN, ANJI, LEO, FAUST, AXL, POTEMKIN, RAMLETHAL, GIO, GOLDLEWIS, BRIDGET, 
-    BEDMAN, ASUKA, JOHNNY, ELPHELT, ABA
+    BEDMAN, ASUKA, JOHNNY, ELPHELT, ABA,
+    /// A character that is not built in, declared in a roster file. Holds the upper case id from the file.
+    Other(String)
 }
 
 impl Display for CharacterId {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            CharacterId::Other(id) => write!(f, "{}", id),
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -61,106 +105,307 @@ impl CharacterId {
         CharacterId::POTEMKIN, CharacterId::RAMLETHAL, CharacterId::GIO, CharacterId::GOLDLEWIS, CharacterId::BRIDGET, 
         CharacterId::BEDMAN, CharacterId::ASUKA, CharacterId::JOHNNY, CharacterId::ELPHELT, CharacterId::ABA
     ];
+
+    /// The built in id with this name (case insensitive, e.g. `sol`), otherwise [`CharacterId::Other`]
+    pub fn parse(id: &str) -> CharacterId {
+        CharacterId::ALL.into_iter()
+            .find(|c| c.to_string().eq_ignore_ascii_case(id.trim()))
+            .unwrap_or_else(|| CharacterId::Other(id.trim().to_uppercase()))
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Character {
     pub id: CharacterId,
+    /// The display name, e.g. `Sol Badguy`
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::regex_serde"))]
     pub regex: Regex,
     pub frame_data_url: String,
-    pub moves: Vec<Move>
+    pub moves: Vec<Move>,
+    /// Read from the overview page, `None` when it could not be loaded (see [`CharacterReport::stats_error`])
+    pub stats: Option<CharacterStats>
 }
 
 impl Character {
-    async fn create(id: CharacterId, regex: &str, frame_data_url: &str) -> Character {
+    async fn create(entry: RosterEntry, regex: Regex, source: Arc<dyn PageSource>) -> (Character, CharacterReport) {
         let mut character = Character {
-            id, 
-            regex: Regex::new(regex).unwrap(), 
-            frame_data_url: String::from(frame_data_url),
-            moves: vec![] 
+            id: entry.id, 
+            name: entry.name,
+            regex, 
+            frame_data_url: entry.frame_data_url,
+            moves: vec![],
+            stats: None
         };
-        character.moves = resolver::get_moves(&character).await;
-        character
+        let (moves, mut report) = resolver::get_moves(&character, source.as_ref()).await;
+        character.moves = moves;
+        match stats::get_stats(&character, source.as_ref()).await {
+            Ok(stats) => character.stats = Some(stats),
+            Err(e) => report.stats_error = Some(e),
+        }
+        (character, report)
+    }
+
+    /// The character's moves of one category, in page order, e.g. all of their overdrives
+    pub fn moves_in(&self, category: MoveCategory) -> Vec<&Move> {
+        self.moves.iter().filter(|m| m.category == category).collect()
+    }
+
+    /// The character's main wiki page, which lists their stats
+    pub fn overview_url(&self) -> String {
+        stats::overview_url(&self.frame_data_url)
     }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::regex_serde"))]
     pub regex: Regex,
     pub input: String,
+    /// The input parsed from its numpad notation, `None` when it isn't written in numpad notation
+    pub notation: Option<Input>,
     pub name: String,
+    pub category: MoveCategory,
+    /// Where `regex` came from
+    pub alias_source: AliasSource,
     pub damage: String,
     pub guard: String,
     pub startup: String, 
     pub active: String,
     pub recovery: String,
+    pub startup_frames: FrameValue,
+    pub active_frames: FrameValue,
+    pub recovery_frames: FrameValue,
     pub onblock: String,
     pub onhit: String,
+    pub onblock_advantage: Advantage,
+    pub onhit_advantage: Advantage,
     pub level: String,
     pub counterhit_type: String,
     pub invuln: String,
     pub proration: String,
     pub risc_gain: String,
     pub risc_loss: String,
-    pub hitboxes: Vec<String>
-}
-
-pub async fn load() -> Result<GGSTDLData, Box<dyn Error>> {
-
-    let characters = vec![
-        Character::create(CharacterId::TESTAMENT, r"(?i)(test)", "https://www.dustloop.com/w/GGST/Testament/Frame_Data"),
-        Character::create(CharacterId::JACKO, r"(?i)(jack)", "https://www.dustloop.com/w/GGST/Jack-O/Frame_Data"),
-        Character::create(CharacterId::NAGORIYUKI, r"(?i)(nago)", "https://www.dustloop.com/w/GGST/Nagoriyuki/Frame_Data"),
-        Character::create(CharacterId::MILLIA, r"(?i)(millia|milia)", "https://www.dustloop.com/w/GGST/Millia_Rage/Frame_Data"),
-        Character::create(CharacterId::CHIPP, r"(?i)(chip)", "https://www.dustloop.com/w/GGST/Chipp_Zanuff/Frame_Data"),
-        Character::create(CharacterId::SOL, r"(?i)(sol)", "https://www.dustloop.com/w/GGST/Sol_Badguy/Frame_Data"),
-        Character::create(CharacterId::KY, r"(?i)(ky)", "https://www.dustloop.com/w/GGST/Ky_Kiske/Frame_Data"),
-        Character::create(CharacterId::MAY, r"(?i)(may)", "https://www.dustloop.com/w/GGST/May/Frame_Data"),
-        Character::create(CharacterId::ZATO, r"(?i)(zato)", "https://www.dustloop.com/w/GGST/Zato-1/Frame_Data"),
-        Character::create(CharacterId::INO, r"(?i)(ino|i-no)", "https://www.dustloop.com/w/GGST/I-No/Frame_Data"),
-        Character::create(CharacterId::HAPPYCHAOS, r"(?i)(hc|chaos|happy)", "https://www.dustloop.com/w/GGST/Happy_Chaos/Frame_Data"),
-        Character::create(CharacterId::SIN, r"(?i)(sin)", "https://www.dustloop.com/w/GGST/Sin_Kiske/Frame_Data"),
-        Character::create(CharacterId::BAIKEN, r"(?i)(baiken)", "https://www.dustloop.com/w/GGST/Baiken/Frame_Data"),
-        Character::create(CharacterId::ANJI, r"(?i)(anji)", "https://www.dustloop.com/w/GGST/Anji_Mito/Frame_Data"),
-        Character::create(CharacterId::LEO, r"(?i)(leo)", "https://www.dustloop.com/w/GGST/Leo_Whitefang/Frame_Data"),
-        Character::create(CharacterId::FAUST, r"(?i)(faust)", "https://www.dustloop.com/w/GGST/Faust/Frame_Data"),
-        Character::create(CharacterId::AXL, r"(?i)(axl)", "https://www.dustloop.com/w/GGST/Axl_Low/Frame_Data"),
-        Character::create(CharacterId::POTEMKIN, r"(?i)(pot)", "https://www.dustloop.com/w/GGST/Potemkin/Frame_Data"),
-        Character::create(CharacterId::RAMLETHAL, r"(?i)(ram)", "https://www.dustloop.com/w/GGST/Ramlethal_Valentine/Frame_Data"),
-        Character::create(CharacterId::GIO, r"(?i)(gio)", "https://www.dustloop.com/w/GGST/Giovanna/Frame_Data"),
-        Character::create(CharacterId::GOLDLEWIS, r"(?i)(lewis|gold|goldlewis|gl|dick)", "https://www.dustloop.com/w/GGST/Goldlewis_Dickinson/Frame_Data"),
-        Character::create(CharacterId::BRIDGET, r"(?i)(bridget)", "https://www.dustloop.com/w/GGST/Bridget/Frame_Data"),
-        Character::create(CharacterId::BEDMAN, r"(?i)(bed)", "https://www.dustloop.com/w/GGST/Bedman/Frame_Data"),
-        Character::create(CharacterId::ASUKA, r"(?i)(asuka)", "https://www.dustloop.com/w/GGST/Asuka_R/Frame_Data"),
-        Character::create(CharacterId::JOHNNY, r"(?i)(joh?nn?y)", "https://www.dustloop.com/w/GGST/Johnny/Frame_Data"),
-        Character::create(CharacterId::ELPHELT, r"(?i)(el)", "https://www.dustloop.com/w/GGST/Elphelt_Valentine/Frame_Data"),
-        Character::create(CharacterId::ABA, r"(?i)(a.?b.?a.?)", "https://www.dustloop.com/w/GGST/A.B.A")
-    ];
+    pub hitboxes: Vec<Hitbox>,
+    /// Columns that don't map to a field above, by header, e.g. `Range`
+    pub extra: BTreeMap<String, String>,
+    /// Tooltips and footnotes found in the row's cells, the text fields above are left without them
+    pub notes: Vec<String>,
+    /// The original HTML of every cell, by column header, for anything the text fields lose
+    pub html: BTreeMap<String, String>
+}
+
+pub async fn load() -> Result<GGSTDLData, GGSTDLError> {
+    load_with(HttpSource::new()).await
+}
+
+/// Loads the frame data from pages previously saved with [`snapshot`] instead of dustloop.com
+pub async fn load_from_dir(path: impl AsRef<Path>) -> Result<GGSTDLData, GGSTDLError> {
+    load_with(DirSource::new(path)).await
+}
+
+/// Loads the frame data from dustloop.com, keeping the pages in `path` and only refetching them once they are older than `ttl`
+pub async fn load_cached(path: impl AsRef<Path>, ttl: Duration) -> Result<GGSTDLData, GGSTDLError> {
+    load_with(CachedSource::new(HttpSource::new(), path, ttl)).await
+}
+
+/// Loads the frame data with pages fetched from any [`PageSource`], e.g. a caching proxy, local mirror or mock server
+pub async fn load_with(source: impl PageSource + 'static) -> Result<GGSTDLData, GGSTDLError> {
+    load_shared(Arc::new(source), &Roster::builtin()).await
+}
+
+/// Loads the frame data of the characters in `roster`, e.g. the built in roster merged with a roster file
+pub async fn load_with_roster(source: impl PageSource + 'static, roster: &Roster) -> Result<GGSTDLData, GGSTDLError> {
+    load_shared(Arc::new(source), roster).await
+}
 
+async fn load_shared(source: Arc<dyn PageSource>, roster: &Roster) -> Result<GGSTDLData, GGSTDLError> {
     let mut set = JoinSet::new();
-    for ele in characters {
-        set.spawn(ele);
+    // which character each task loads, so a task that panics still shows up in the report
+    let mut tasks = HashMap::new();
+    for entry in &roster.entries {
+        let regex = Regex::new(&entry.regex)
+            .map_err(|e| GGSTDLError::InvalidAliasRegex { pattern: entry.regex.clone(), message: e.to_string() })?;
+        let task = set.spawn(Character::create(entry.clone(), regex, source.clone()));
+        tasks.insert(task.id(), (entry.id.clone(), entry.frame_data_url.clone()));
     }
 
     let mut characters: Vec<Character> = vec![];
-    while let Some(res) = set.join_next().await {
-        let Ok(character) = res else {
-            println!("Error handling character creation future: {}", res.unwrap_err());
-            continue;
-        };
-        characters.push(character);
+    let mut report = LoadReport::default();
+    while let Some(res) = set.join_next_with_id().await {
+        match res {
+            Ok((_, (character, character_report))) => {
+                characters.push(character);
+                report.characters.push(character_report);
+            },
+            Err(e) => {
+                let Some((id, url)) = tasks.remove(&e.id()) else {
+                    continue;
+                };
+                let mut character_report = CharacterReport::new(id.clone(), url);
+                character_report.error = Some(GGSTDLError::LoadFailed { character: id, message: e.to_string() });
+                report.characters.push(character_report);
+            },
+        }
     }
+    // tasks finish in whatever order the network allows, sort so the data is the same from run to run
+    characters.sort_by(|a, b| a.id.cmp(&b.id));
+    report.characters.sort_by(|a, b| a.id.cmp(&b.id));
 
     Ok(GGSTDLData {
-        characters
+        characters,
+        report
     })
 }
 
+/// Saves the current dustloop.com frame data and overview page of every character into `path` so it can be read with [`load_from_dir`].
+/// The GGST index page is saved too, so [`discover`] can run against the directory.
+pub async fn snapshot(path: impl AsRef<Path>) -> Result<(), GGSTDLError> {
+    let io_error = |path: &Path, e: std::io::Error| GGSTDLError::Io { path: path.display().to_string(), message: e.to_string() };
+    tokio::fs::create_dir_all(path.as_ref()).await.map_err(|e| io_error(path.as_ref(), e))?;
+    let source = HttpSource::new();
+    let dir = DirSource::new(path);
+    let requests = Roster::builtin().entries.into_iter()
+        .flat_map(|entry| {
+            let overview = stats::overview_url(&entry.frame_data_url);
+            [PageRequest::character(entry.id.clone(), entry.frame_data_url), PageRequest::overview(entry.id, overview)]
+        })
+        .chain([PageRequest::url(INDEX_URL)]);
+    for request in requests {
+        let page = source.fetch(&request).await?;
+        if !page.is_success() {
+            return Err(GGSTDLError::Http { url: request.url, status: page.status.unwrap_or_default() });
+        }
+        let file = dir.path_for(&request);
+        tokio::fs::write(&file, page.body).await.map_err(|e| io_error(&file, e))?;
+    }
+    Ok(())
+}
+
+/// The built in roster loaded from the saved Sol page, every other character ends up without moves
+#[cfg(test)]
+async fn load_sol_fixture() -> GGSTDLData {
+    let mut source = MemorySource::new();
+    source.insert_character(CharacterId::SOL, include_str!("../fixtures/SOL.html"));
+    load_with(source).await.unwrap()
+}
+
 #[tokio::test]
 async fn test() {
     let _load = load().await.unwrap();
     // let character = load.find_character("chaos").unwrap();
     // for move_f in &character.moves {
-    //     println!("{:?}", move_f.hitboxes);
+    //     println!("{:?}", move_f.hitboxes.iter().map(|h| &h.url).collect::<Vec<_>>());
     // }
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_fixtures() {
+    let load = load_from_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).await.unwrap();
+    let found = load.find_move("sol", "5P").unwrap();
+    assert_eq!(found.startup_frames, FrameValue::Single(4));
+    let found = load.find_move("sol", "vortex").unwrap();
+    assert_eq!(found.name, "Night Raid Vortex");
+    assert_eq!(found.onhit_advantage.effect, Some(AdvantageEffect::WallStick));
+    assert_eq!(load.find_move("sol", "qcf P").unwrap().name, "Gun Flame");
+    assert_eq!(load.find_move("sol", "dp HS").unwrap().input, "623H");
+    assert_eq!(found.category, MoveCategory::Special);
+    let sol = load.find_character("sol").unwrap();
+    let overdrives = sol.moves_in(MoveCategory::Overdrive).iter().map(|m| m.name.as_str()).collect::<Vec<&str>>();
+    assert_eq!(overdrives, vec!["Tyrant Rave", "Heavy Mob Cemetery"]);
+    assert_eq!(load.find_move("sol", "6P").unwrap().category, MoveCategory::CommandNormal);
+    let dragon_punches = sol.moves.iter().filter(|m| m.notation.as_ref().is_some_and(|n| n.has_motion("623"))).count();
+    assert_eq!(dragon_punches, 2);
+
+    let stats = load.find_character("sol").unwrap().stats.as_ref().unwrap();
+    assert_eq!(stats.defense, Some(0.96));
+    assert_eq!(stats.backdash, Some(20));
+    assert_eq!(stats.backdash_invuln, FrameValue::Range(1, 7));
+    assert_eq!(stats.movement_options, vec!["Dash Cancel"]);
+    assert!(load.find_character("ky").unwrap().stats.is_none());
+    assert!(matches!(load.report.get(&CharacterId::KY).unwrap().stats_error, Some(GGSTDLError::Io { .. })));
+}
+
+#[tokio::test]
+async fn test_memory_source() {
+    let load = load_sol_fixture().await;
+    assert_eq!(load.find_character("sol").unwrap().moves.len(), 19);
+    assert!(load.find_character("ky").unwrap().moves.is_empty());
+
+    let sol = load.report.get(&CharacterId::SOL).unwrap();
+    assert!(sol.is_complete());
+    assert_eq!(sol.sections_found, vec!["Normal Moves", "Special Moves", "Overdrives", "Throws", "Wild Assault", "Beta Moves"]);
+    assert_eq!(sol.sections_unknown, vec!["Beta Moves"]);
+    assert!(sol.sections_missing.is_empty());
+    let throw = load.find_move("sol", "j.6D").unwrap();
+    assert_eq!(throw.category, MoveCategory::Throw);
+    // the throw table has its own column order and a column Move has no field for
+    assert_eq!(throw.guard, "Throw");
+    assert_eq!(throw.onhit, "KD");
+    assert!(throw.onblock.is_empty());
+    assert_eq!(throw.extra.get("Range").map(String::as_str), Some("160"));
+
+    let gun_flame = load.find_move("sol", "236P").unwrap();
+    assert_eq!(gun_flame.recovery, "Total 59");
+    assert_eq!(gun_flame.notes, vec!["Shorter when the flame is cancelled with a follow up."]);
+    assert!(gun_flame.html["Recovery"].contains("cite_note-1"));
+    let close_slash = load.find_move("sol", "c.S").unwrap();
+    assert_eq!(close_slash.onblock, "+1");
+    assert_eq!(close_slash.notes, vec!["+3 when blocked at max range"]);
+    assert_eq!(load.find_move("sol", "hmc").unwrap().invuln, "1-10 All\n11-20 Strike");
+
+    let bandit_revolver = load.find_move("sol", "236K").unwrap();
+    let captions = bandit_revolver.hitboxes.iter().map(|h| (h.order, h.caption.as_deref())).collect::<Vec<_>>();
+    assert_eq!(captions, vec![(0, Some("First hit")), (1, Some("Second hit"))]);
+    assert_eq!(bandit_revolver.hitboxes[1].url, "https://www.dustloop.com/images/f/f0/GGST_Sol_Badguy_BR_Hitbox2.png");
+    let fafnir = load.find_character("sol").unwrap().moves.iter().find(|m| m.name == "Fafnir").unwrap();
+    assert_eq!(fafnir.category, MoveCategory::System);
+    assert!(matches!(load.report.get(&CharacterId::KY).unwrap().error, Some(GGSTDLError::Fetch { .. })));
+    assert_eq!(load.report.empty_characters().len(), 26);
+}
+
+#[tokio::test]
+async fn test_lookup_errors() {
+    let load = load_sol_fixture().await;
+    assert_eq!(load.find_character("nobody").unwrap_err(), GGSTDLError::UnknownCharacter { query: String::from("nobody") });
+    assert_eq!(load.find_move("sol", "nothing").unwrap_err(), GGSTDLError::UnknownMove { character: CharacterId::SOL, query: String::from("nothing") });
+}
+
+#[tokio::test]
+async fn test_character_resolution() {
+    let load = load_with(MemorySource::new()).await.unwrap();
+    assert!(load.characters.windows(2).all(|w| w[0].id < w[1].id));
+    assert_eq!(load.find_character("sol").unwrap().id, CharacterId::SOL);
+    assert_eq!(load.find_character("Sin Kiske").unwrap().id, CharacterId::SIN);
+    assert_eq!(load.find_character("jack-o").unwrap().id, CharacterId::JACKO);
+    assert_eq!(load.find_character("el").unwrap().id, CharacterId::ELPHELT);
+    assert_eq!(load.find_character("kiske").unwrap_err(), GGSTDLError::AmbiguousQuery {
+        query: String::from("kiske"),
+        candidates: vec![String::from("Ky Kiske"), String::from("Sin Kiske")],
+    });
+}
+
+#[cfg(test)]
+struct PanickingSource;
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl PageSource for PanickingSource {
+    async fn fetch(&self, request: &PageRequest) -> FetchResult {
+        if request.character == Some(CharacterId::KY) {
+            panic!("broken source");
+        }
+        Err(GGSTDLError::Fetch { url: request.url.clone(), message: String::from("unavailable") })
+    }
+}
+
+#[tokio::test]
+async fn test_panicking_character() {
+    let load = load_with(PanickingSource).await.unwrap();
+    assert_eq!(load.report.characters.len(), CharacterId::ALL.len());
+    let ky = load.report.get(&CharacterId::KY).unwrap();
+    assert!(matches!(ky.error, Some(GGSTDLError::LoadFailed { .. })));
+    assert!(ky.url.ends_with("Ky_Kiske/Frame_Data"));
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::{resolver, search, CharacterId, GGSTDLData, GGSTDLError, MemorySource, MoveAliases, Roster};
+
+/// Problems with the alias tables found by [`GGSTDLData::lint`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintReport {
+    pub dead_bindings: Vec<DeadBinding>,
+    pub unreachable_moves: Vec<UnreachableMove>,
+    pub ambiguous_queries: Vec<AmbiguousQuery>,
+}
+
+/// A built in or user alias whose target is neither the input nor the name of any loaded move, so it is never used.
+/// User aliases for a character that was not loaded at all are reported here too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadBinding {
+    pub character: CharacterId,
+    pub pattern: String,
+    pub target: String,
+}
+
+/// A move that can't be found by its own input or name, because another move's alias matches first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnreachableMove {
+    pub character: CharacterId,
+    pub input: String,
+    pub name: String,
+    /// The input of the move its input resolves to instead, if any
+    pub shadowed_by: Option<String>,
+}
+
+/// An input or name of a move that matches several moves, only the first of which can be found with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmbiguousQuery {
+    pub character: CharacterId,
+    pub query: String,
+    /// Inputs of every matching move, the one found first
+    pub matches: Vec<String>,
+}
+
+impl GGSTDLData {
+    /// Checks the alias tables against the loaded moves. Characters without moves are skipped, as every alias of
+    /// theirs would show up as dead.
+    pub fn lint(&self) -> LintReport {
+        self.lint_with_aliases(&MoveAliases::default())
+    }
+
+    /// Checks the built in alias tables and the user's `aliases` against the loaded moves, see [`GGSTDLData::lint`]
+    pub fn lint_with_aliases(&self, aliases: &MoveAliases) -> LintReport {
+        let mut report = LintReport::default();
+        for character in self.characters.iter().filter(|c| !c.moves.is_empty()) {
+            let user_aliases = aliases.entries.iter()
+                .filter(|a| a.character == character.id)
+                .map(|a| (&a.regex, &a.target));
+            for (regex, target) in resolver::bindings(&character.id).iter().map(|(r, t)| (r, t)).chain(user_aliases) {
+                if !character.moves.iter().any(|m| search::is_target(m, target)) {
+                    report.dead_bindings.push(DeadBinding {
+                        character: character.id.clone(),
+                        pattern: regex.as_str().to_string(),
+                        target: target.clone(),
+                    });
+                }
+            }
+
+            for move_found in &character.moves {
+                let by_input = search::resolve_move(character, aliases, &move_found.input).ok();
+                let by_name = search::resolve_move(character, aliases, &move_found.name).ok();
+                let reachable = [&by_input, &by_name].iter()
+                    .any(|r| r.as_ref().is_some_and(|r| std::ptr::eq(r.found, move_found)));
+                if !reachable {
+                    report.unreachable_moves.push(UnreachableMove {
+                        character: character.id.clone(),
+                        input: move_found.input.clone(),
+                        name: move_found.name.clone(),
+                        shadowed_by: by_input.as_ref().map(|r| r.found.input.clone()),
+                    });
+                }
+
+                for (query, resolution) in [(&move_found.input, by_input), (&move_found.name, by_name)] {
+                    let Some(resolution) = resolution.filter(|r| !r.other_matches.is_empty()) else {
+                        continue;
+                    };
+                    if report.ambiguous_queries.iter().any(|a| a.character == character.id && a.query.eq_ignore_ascii_case(query)) {
+                        continue;
+                    }
+                    report.ambiguous_queries.push(AmbiguousQuery {
+                        character: character.id.clone(),
+                        query: query.clone(),
+                        matches: std::iter::once(resolution.found).chain(resolution.other_matches).map(|m| m.input.clone()).collect(),
+                    });
+                }
+            }
+        }
+        for alias in aliases.entries.iter().filter(|a| !self.characters.iter().any(|c| c.id == a.character)) {
+            report.dead_bindings.push(DeadBinding {
+                character: alias.character.clone(),
+                pattern: alias.regex.as_str().to_string(),
+                target: alias.target.clone(),
+            });
+        }
+        report
+    }
+}
+
+impl LintReport {
+    pub fn is_clean(&self) -> bool {
+        self.dead_bindings.is_empty() && self.unreachable_moves.is_empty() && self.ambiguous_queries.is_empty()
+    }
+}
+
+/// Loads a single character from a saved frame data page and lints it, for checking alias changes in tests:
+/// `assert!(lint_fixture(CharacterId::SOL, include_str!("SOL.html")).await?.unreachable_moves.is_empty())`
+pub async fn lint_fixture(character: CharacterId, html: &str) -> Result<LintReport, GGSTDLError> {
+    let roster = Roster { entries: Roster::builtin().entries.into_iter().filter(|e| e.id == character).collect() };
+    if roster.entries.is_empty() {
+        return Err(GGSTDLError::UnknownCharacter { query: character.to_string() });
+    }
+    let mut source = MemorySource::new();
+    source.insert_character(character, html);
+    Ok(crate::load_with_roster(source, &roster).await?.lint())
+}
+
+impl Display for LintReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for dead in &self.dead_bindings {
+            writeln!(f, "{}: alias {} targets '{}', which no move has", dead.character, dead.pattern, dead.target)?;
+        }
+        for unreachable in &self.unreachable_moves {
+            write!(f, "{}: {} ({}) can't be found by its input or name", unreachable.character, unreachable.input, unreachable.name)?;
+            match &unreachable.shadowed_by {
+                Some(other) => writeln!(f, ", {} is found instead", other)?,
+                None => writeln!(f)?,
+            }
+        }
+        for ambiguous in &self.ambiguous_queries {
+            writeln!(f, "{}: '{}' matches {}", ambiguous.character, ambiguous.query, ambiguous.matches.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn lint_sol_fixture() {
+    let report = lint_fixture(CharacterId::SOL, include_str!("../fixtures/SOL.html")).await.unwrap();
+    let dead = report.dead_bindings.iter().map(|d| d.target.as_str()).collect::<Vec<&str>>();
+    assert!(dead.contains(&"Wild Throw") && dead.contains(&"j.633H"));
+    assert!(!dead.contains(&"Gun Flame") && !dead.contains(&"Fafnir"));
+    let fafnir = report.ambiguous_queries.iter().find(|a| a.query == "41236K").unwrap();
+    assert_eq!(fafnir.matches, vec!["41236K", "236K"]);
+    assert!(!report.is_clean());
+    assert!(report.to_string().contains("alias"));
+}
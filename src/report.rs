@@ -0,0 +1,133 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::{CharacterId, GGSTDLError};
+
+/// What happened while loading every character, so missing or partial data can be noticed
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    pub characters: Vec<CharacterReport>,
+}
+
+/// What happened while loading a single character's frame data page
+#[derive(Debug, Clone)]
+pub struct CharacterReport {
+    pub id: CharacterId,
+    pub url: String,
+    /// The HTTP status of the page, if the source fetched it over HTTP
+    pub status: Option<u16>,
+    /// Why the page could not be loaded at all
+    pub error: Option<GGSTDLError>,
+    /// The headings of the frame data tables found, in page order
+    pub sections_found: Vec<String>,
+    /// Sections every character is expected to have that were not found
+    pub sections_missing: Vec<String>,
+    /// Headings of frame data tables that did not match a [`crate::MoveCategory`], their moves are loaded as
+    /// [`crate::MoveCategory::System`]
+    pub sections_unknown: Vec<String>,
+    pub rows_parsed: usize,
+    /// Rows that could not be turned into moves, always [`GGSTDLError::MalformedRow`]
+    pub rows_skipped: Vec<GGSTDLError>,
+    /// Why the character's stats could not be read from their overview page
+    pub stats_error: Option<GGSTDLError>,
+}
+
+impl LoadReport {
+    pub fn get(&self, id: &CharacterId) -> Option<&CharacterReport> {
+        self.characters.iter().find(|c| &c.id == id)
+    }
+
+    /// Characters that ended up without a single move
+    pub fn empty_characters(&self) -> Vec<CharacterId> {
+        self.characters.iter().filter(|c| c.rows_parsed == 0).map(|c| c.id.clone()).collect()
+    }
+
+    pub fn rows_parsed(&self) -> usize {
+        self.characters.iter().map(|c| c.rows_parsed).sum()
+    }
+
+    /// Whether this report describes a less complete load than `other`: more characters that failed to load, more
+    /// missing sections or skipped rows, or a character that had moves and now has none. Fewer rows alone is not
+    /// worse, a patch can remove or merge moves.
+    pub fn is_worse_than(&self, other: &LoadReport) -> bool {
+        let failures = |report: &LoadReport| (
+            report.characters.iter().filter(|c| c.error.is_some()).count(),
+            report.characters.iter().map(|c| c.sections_missing.len()).sum::<usize>(),
+            report.characters.iter().map(|c| c.rows_skipped.len()).sum::<usize>(),
+        );
+        let (errors, missing, skipped) = failures(self);
+        let (other_errors, other_missing, other_skipped) = failures(other);
+        let emptied = other.characters.iter()
+            .filter(|c| c.rows_parsed > 0)
+            .any(|c| self.get(&c.id).is_none_or(|now| now.rows_parsed == 0));
+        emptied || errors > other_errors || missing > other_missing || skipped > other_skipped
+    }
+
+    /// Whether every character loaded without errors, missing sections or skipped rows
+    pub fn is_complete(&self) -> bool {
+        self.characters.iter().all(CharacterReport::is_complete)
+    }
+}
+
+impl CharacterReport {
+    pub fn new(id: CharacterId, url: impl Into<String>) -> CharacterReport {
+        CharacterReport {
+            id,
+            url: url.into(),
+            status: None,
+            error: None,
+            sections_found: vec![],
+            sections_missing: vec![],
+            sections_unknown: vec![],
+            rows_parsed: 0,
+            rows_skipped: vec![],
+            stats_error: None,
+        }
+    }
+
+    /// Every problem found for this character as errors, so they can be matched on
+    pub fn errors(&self) -> Vec<GGSTDLError> {
+        let missing = self.sections_missing.iter()
+            .map(|section| GGSTDLError::MissingSection { character: self.id.clone(), section: section.clone() });
+        self.error.iter().cloned().chain(missing).chain(self.rows_skipped.iter().cloned()).chain(self.stats_error.iter().cloned()).collect()
+    }
+
+    /// Whether the frame data loaded without problems, a missing overview page only shows up in [`CharacterReport::stats_error`]
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none() && self.sections_missing.is_empty() && self.rows_skipped.is_empty() && self.rows_parsed > 0
+    }
+}
+
+impl Display for LoadReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for character in &self.characters {
+            writeln!(f, "{}", character)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for CharacterReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} rows parsed", self.id, self.rows_parsed)?;
+        if let Some(status) = self.status {
+            write!(f, ", HTTP {}", status)?;
+        }
+        if let Some(error) = &self.error {
+            write!(f, ", error: {}", error)?;
+        }
+        if !self.sections_missing.is_empty() {
+            write!(f, ", missing sections: {}", self.sections_missing.join(", "))?;
+        }
+        if !self.sections_unknown.is_empty() {
+            write!(f, ", unknown sections: {}", self.sections_unknown.join(", "))?;
+        }
+        if !self.rows_skipped.is_empty() {
+            write!(f, ", {} rows skipped", self.rows_skipped.len())?;
+        }
+        if let Some(error) = &self.stats_error {
+            write!(f, ", stats error: {}", error)?;
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::OnceLock;
 
 use regex::Regex;
-use scraper::{Selector, ElementRef, element_ref::Select};
+use scraper::{Selector, ElementRef};
 
-use crate::{Move, CharacterId, Character};
-
-fn get_image_url_matcher() -> &'static Regex {
-    static IMAGE_URL_MATCHER: OnceLock<Regex> = OnceLock::new();
-    IMAGE_URL_MATCHER.get_or_init(|| Regex::new(r"(?i)src=&quot;(\S*(hitbox|HB)\S*\.png)").unwrap())
-}
+use crate::{cell, hitbox};
+use crate::{AliasSource, Input, Move, CharacterId, Character, FrameValue, Advantage, PageRequest, PageSource, CharacterReport, GGSTDLError, MoveCategory};
 
 fn get_row_selector() -> &'static Selector {
     static ROW_SELECTOR: OnceLock<Selector> = OnceLock::new();
@@ -21,92 +17,232 @@ fn get_element_selector() -> &'static Selector {
     ELEMENT_SELECTOR.get_or_init(|| Selector::parse("td").unwrap())
 }
 
-const SECTIONS: [&str; 3] = ["#section-collapsible-3 > table", "#section-collapsible-4 > table", "#section-collapsible-5 > table"];
-pub async fn get_moves(character: &Character) -> Vec<Move> {
+fn get_headline_selector() -> &'static Selector {
+    static HEADLINE_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    HEADLINE_SELECTOR.get_or_init(|| Selector::parse(".mw-headline").unwrap())
+}
+
+fn get_table_selector() -> &'static Selector {
+    static TABLE_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    TABLE_SELECTOR.get_or_init(|| Selector::parse("table").unwrap())
+}
+
+fn get_header_selector() -> &'static Selector {
+    static HEADER_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    HEADER_SELECTOR.get_or_init(|| Selector::parse("tr > th").unwrap())
+}
+
+// every character page is expected to have these, a page without one of them probably changed layout
+const REQUIRED_SECTIONS: [(MoveCategory, &str); 3] = [
+    (MoveCategory::Normal, "Normal Moves"),
+    (MoveCategory::Special, "Special Moves"),
+    (MoveCategory::Overdrive, "Overdrives"),
+];
+
+pub async fn get_moves(character: &Character, source: &dyn PageSource) -> (Vec<Move>, CharacterReport) {
+    let mut report = CharacterReport::new(character.id.clone(), character.frame_data_url.as_str());
     let mut moves: Vec<Move> = vec![];
 
-    let res = reqwest::get(character.frame_data_url.as_str()).await;
-    let Ok(res) = res else {
-        println!("Error making request for {:?}", character.id);
-        return moves;
-    };
-    let Ok(res) = res.text().await else {
-        println!("Error making request for {:?}", character.id);
-        return moves;
+    let res = source.fetch(&PageRequest::character(character.id.clone(), character.frame_data_url.as_str())).await;
+    let res = match res {
+        Ok(res) => res,
+        Err(e) => {
+            report.error = Some(e);
+            return (moves, report);
+        }
     };
+    report.status = res.status;
+    if !res.is_success() {
+        report.error = Some(GGSTDLError::Http { url: character.frame_data_url.clone(), status: res.status.unwrap_or_default() });
+        return (moves, report);
+    }
 
-    let document = scraper::Html::parse_document(&res);
-    for (ind, ele) in SECTIONS.iter().enumerate() {
-        let parse = Selector::parse(ele);
-        let Ok(section_selector) = parse else {
-            println!("Error making selector for {:?}: {}", character.id, parse.unwrap_err());
-            continue;
-        };
-        let select = document.select(&section_selector).next();
-        let Some(section_element) = select else {
-            println!("Could not select section {} for {:?}", ele, character.id);
+    let document = scraper::Html::parse_document(&res.body);
+    let footnotes = cell::footnotes(&document);
+    let mut categories_found = vec![];
+    for table in document.select(get_table_selector()) {
+        let headers = table_headers(table);
+        // tables without an input column are layout or navigation, not frame data
+        if !headers.iter().any(|h| column_field(h) == Some("input")) {
             continue;
+        }
+        let heading = section_heading(table).unwrap_or_default();
+        let category = match MoveCategory::from_heading(&heading) {
+            Some(category) => category,
+            None => {
+                report.sections_unknown.push(heading.clone());
+                MoveCategory::default()
+            }
         };
-        let mut moves_found = load_section(character.id, section_element, ind != 0);
+        categories_found.push(category);
+        report.sections_found.push(heading.clone());
+        let mut moves_found = load_section(&character.id, &heading, table, &headers, category, &footnotes, &mut report);
         moves.append(&mut moves_found);
     }
-    moves
+    for (category, name) in REQUIRED_SECTIONS {
+        if !categories_found.contains(&category) {
+            report.sections_missing.push(name.to_string());
+        }
+    }
+    report.rows_parsed = moves.len();
+    (moves, report)
+}
+
+// the text of every header cell, in column order
+fn table_headers(table: ElementRef) -> Vec<String> {
+    let Some(header_row) = table.select(get_header_selector()).next().and_then(|th| th.parent()).and_then(ElementRef::wrap) else {
+        return vec![];
+    };
+    header_row.children()
+        .filter_map(ElementRef::wrap)
+        .map(|cell| cell.text().collect::<String>().split_whitespace().collect::<Vec<&str>>().join(" "))
+        .collect()
+}
+
+// the text of the closest heading before the table, found by walking back through the table and its parents
+fn section_heading(table: ElementRef) -> Option<String> {
+    let heading_names = ["h1", "h2", "h3", "h4"];
+    for node in std::iter::once(*table).chain(table.ancestors()) {
+        let heading = node.prev_siblings()
+            .filter_map(ElementRef::wrap)
+            .find(|e| heading_names.contains(&e.value().name()) || e.select(get_headline_selector()).next().is_some());
+        if let Some(heading) = heading {
+            let headline = heading.select(get_headline_selector()).next().unwrap_or(heading);
+            return Some(headline.text().collect::<String>().trim().to_string());
+        }
+    }
+    None
 }
 
-fn load_section(character: CharacterId, section: ElementRef, named: bool) -> Vec<Move> {
+// the Move field a column is read into, matched loosely so `On-Block`, `On Block` and `onblock` are the same column
+fn column_field(header: &str) -> Option<&'static str> {
+    let key = header.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    let field = match key.as_str() {
+        "input" => "input",
+        "name" => "name",
+        "damage" => "damage",
+        "guard" => "guard",
+        "startup" => "startup",
+        "active" => "active",
+        "recovery" => "recovery",
+        "onblock" => "onblock",
+        "onhit" => "onhit",
+        "level" => "level",
+        "countertype" | "counterhittype" => "counterhit_type",
+        "invuln" | "invulnerability" => "invuln",
+        "proration" => "proration",
+        "riscgain" => "risc_gain",
+        "riscloss" => "risc_loss",
+        _ => return None,
+    };
+    Some(field)
+}
+
+fn load_section(
+    character: &CharacterId,
+    name: &str,
+    section: ElementRef,
+    headers: &[String],
+    category: MoveCategory,
+    footnotes: &HashMap<String, String>,
+    report: &mut CharacterReport,
+) -> Vec<Move> {
+    let input_column = headers.iter().position(|h| column_field(h) == Some("input"));
     let select = section.select(get_row_selector());
     let mut moves: Vec<Move> = vec![];
-    for row_raw in select {
-        // the hitbox image urls are in the html element itself (hidden details control)
-        let element_html = row_raw.html();
-        let mut hitboxes = vec![];
-        // get all hitboxes for this move
-        for (_, [url, _]) in get_image_url_matcher().captures_iter(&element_html).map(|c| c.extract()) {
-            // the first (0th) capture is always the entire match, I just want the first group as designed in the regex
-            hitboxes.push(format!("https://www.dustloop.com{}", url));
+    for (ind, row_raw) in select.enumerate() {
+        let mut row_elements = row_raw.select(get_element_selector()).peekable();
+        if row_elements.peek().is_none() {
+            report.rows_skipped.push(GGSTDLError::MalformedRow {
+                character: character.clone(), section: name.to_string(), row: ind, column: None, reason: String::from("Row has no cells")
+            });
+            continue;
+        }
+        let mut move_found = parse_row(row_elements, headers, character, footnotes);
+        if move_found.input.is_empty() {
+            report.rows_skipped.push(GGSTDLError::MalformedRow {
+                character: character.clone(), section: name.to_string(), row: ind, column: input_column, reason: String::from("Row has no input")
+            });
+            continue;
         }
-        let row_elements = row_raw.select(get_element_selector());
-        let mut move_found = parse_row(row_elements, &character, named);
-        move_found.hitboxes = hitboxes;
+        move_found.hitboxes = hitbox::row_hitboxes(row_raw);
+        move_found.category = category.refine(&move_found.input, &move_found.name);
         moves.push(move_found);
     }
     moves
 }
 
-// an unfortunate special boolean I have to include because the rows are not the same for Normals, Specials etc
-fn parse_row(row: Select, character_id: &CharacterId, named: bool) -> Move {
-    let mut row = row.map(|v| v.inner_html());
-    let _ = row.next(); // skip one, first is details control on table
-    let input = row.next().unwrap_or(String::from("")).trim().to_string();
-    let name = if named {
-        row.next().unwrap_or(String::from("")).trim().to_string()
-    } else {
-        input.clone()
+// cells are matched to fields by the header of their column, columns without a field end up in `extra`
+fn parse_row<'a>(
+    row: impl Iterator<Item = ElementRef<'a>>,
+    headers: &[String],
+    character_id: &CharacterId,
+    footnotes: &HashMap<String, String>,
+) -> Move {
+    let mut fields: HashMap<&str, String> = HashMap::new();
+    let mut extra = BTreeMap::new();
+    let mut html = BTreeMap::new();
+    let mut notes: Vec<String> = vec![];
+    for (header, cell) in headers.iter().zip(row) {
+        // the details control column holding the hitbox images has no header
+        if header.is_empty() {
+            continue;
+        }
+        let cleaned = cell::clean(cell, footnotes);
+        html.insert(header.clone(), cell.inner_html().trim().to_string());
+        for note in cleaned.notes {
+            if !notes.contains(&note) {
+                notes.push(note);
+            }
+        }
+        let value = cleaned.text;
+        match column_field(header) {
+            Some(field) => {
+                fields.insert(field, value);
+            },
+            None => {
+                extra.insert(header.clone(), value);
+            },
+        }
+    }
+    let mut field = |name: &str| fields.remove(name).unwrap_or_default();
+    let input = field("input");
+    // normals have no name column, they go by their input
+    let name = Some(field("name")).filter(|n| !n.is_empty()).unwrap_or_else(|| input.clone());
+    let damage = field("damage");
+    let guard = field("guard");
+    let startup = field("startup");
+    let active = field("active");
+    let recovery = field("recovery");
+    let onblock = field("onblock");
+    let onhit = field("onhit");
+    let level = field("level");
+    let counterhit_type = field("counterhit_type");
+    let invuln = field("invuln");
+    let proration = field("proration");
+    let risc_gain = field("risc_gain");
+    let risc_loss = field("risc_loss");
+    let (regex, alias_source) = match get_regex_binding(character_id, input.clone(), name.clone()) {
+        Some(regex) => (regex, AliasSource::Binding),
+        None => (default_normal_resolver(input.clone()), AliasSource::DefaultResolver),
     };
-    let damage = row.next().unwrap_or(String::from("")).trim().to_string();
-    let guard = row.next().unwrap_or(String::from("")).trim().to_string();
-    let startup = row.next().unwrap_or(String::from("")).trim().to_string();
-    let active = row.next().unwrap_or(String::from("")).trim().to_string();
-    let recovery = row.next().unwrap_or(String::from("")).trim().to_string();
-    let onblock = row.next().unwrap_or(String::from("")).trim().to_string();
-    let onhit = row.next().unwrap_or(String::from("")).trim().to_string();
-    let level = row.next().unwrap_or(String::from("")).trim().to_string();
-    let counterhit_type = row.next().unwrap_or(String::from("")).trim().to_string();
-    let invuln = row.next().unwrap_or(String::from("")).trim().to_string();
-    let proration = row.next().unwrap_or(String::from("")).trim().to_string();
-    let risc_gain = row.next().unwrap_or(String::from("")).trim().to_string();
-    let risc_loss = row.next().unwrap_or(String::from("")).trim().to_string();
-    let regex = get_regex_binding(character_id, input.clone(), name.clone())
-        .unwrap_or(default_normal_resolver(input.clone()));
     Move {
         regex,
+        alias_source,
+        notation: Input::parse(&input),
         input,
         name,
+        category: MoveCategory::default(),
         damage,
         guard,
+        startup_frames: FrameValue::parse(&startup),
+        active_frames: FrameValue::parse(&active),
+        recovery_frames: FrameValue::parse(&recovery),
         startup,
         active,
         recovery,
+        onblock_advantage: Advantage::parse(&onblock),
+        onhit_advantage: Advantage::parse(&onhit),
         onblock,
         onhit,
         level,
@@ -116,6 +252,9 @@ fn parse_row(row: Select, character_id: &CharacterId, named: bool) -> Move {
         risc_gain,
         risc_loss,
         hitboxes: vec![],
+        extra,
+        notes,
+        html,
     }
 }
 
@@ -131,6 +270,11 @@ fn get_loaded_move_bindings() -> &'static HashMap<CharacterId, Vec<(Regex, Strin
     BINDINGS.get_or_init(get_all_bindings)
 }
 
+/// The built in aliases of a character as (alias regex, input or name it is for) pairs, in priority order
+pub(crate) fn bindings(character_id: &CharacterId) -> &'static [(Regex, String)] {
+    get_loaded_move_bindings().get(character_id).map(Vec::as_slice).unwrap_or_default()
+}
+
 fn get_regex_binding(character_id: &CharacterId, input: String, name: String) -> Option<Regex> {
     get_loaded_move_bindings().get(character_id).and_then(|v| {
         for ele in v {
@@ -148,7 +292,7 @@ fn get_all_bindings() -> HashMap<CharacterId, Vec<(Regex, String)>> {
     let mut total: HashMap<CharacterId, Vec<(Regex, String)>> = HashMap::default();
     for char_id in CharacterId::ALL {
         let mut moves: Vec<(Regex, String)> = vec![];
-        for (k, v) in get_bindings(char_id) {
+        for (k, v) in get_bindings(&char_id) {
             moves.push((Regex::new(k.as_str()).unwrap(), v));
         }
         total.insert(char_id, moves);
@@ -156,7 +300,7 @@ fn get_all_bindings() -> HashMap<CharacterId, Vec<(Regex, String)>> {
     total
 }
 
-fn get_bindings(character_id: CharacterId) -> Vec<(String, String)> {
+fn get_bindings(character_id: &CharacterId) -> Vec<(String, String)> {
     match character_id {
         CharacterId::TESTAMENT => {
             vec![
@@ -640,6 +784,7 @@ fn get_bindings(character_id: CharacterId) -> Vec<(String, String)> {
             vec![
                 /* TODO! */
             ]
-        }
+        },
+        CharacterId::Other(_) => vec![]
     }.into_iter().map(|(k, v)| (String::from(k), String::from(v))).collect::<Vec<(String, String)>>()
 }
\ No newline at end of file
@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use crate::{search, CharacterId, GGSTDLError};
+
+/// The characters to load
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Roster {
+    pub entries: Vec<RosterEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RosterEntry {
+    pub id: CharacterId,
+    /// The display name, e.g. `Sol Badguy`
+    pub name: String,
+    /// Case insensitive regex of the names the character can be found by
+    pub regex: String,
+    pub frame_data_url: String,
+}
+
+impl RosterEntry {
+    pub fn new(id: CharacterId, name: impl Into<String>, regex: impl Into<String>, frame_data_url: impl Into<String>) -> RosterEntry {
+        RosterEntry { id, name: name.into(), regex: regex.into(), frame_data_url: frame_data_url.into() }
+    }
+}
+
+impl Roster {
+    /// Every character this version of the crate knows about
+    pub fn builtin() -> Roster {
+        let entries = vec![
+            RosterEntry::new(CharacterId::TESTAMENT, "Testament", r"(?i)(test)", "https://www.dustloop.com/w/GGST/Testament/Frame_Data"),
+            RosterEntry::new(CharacterId::JACKO, "Jack-O", r"(?i)(jack)", "https://www.dustloop.com/w/GGST/Jack-O/Frame_Data"),
+            RosterEntry::new(CharacterId::NAGORIYUKI, "Nagoriyuki", r"(?i)(nago)", "https://www.dustloop.com/w/GGST/Nagoriyuki/Frame_Data"),
+            RosterEntry::new(CharacterId::MILLIA, "Millia Rage", r"(?i)(millia|milia)", "https://www.dustloop.com/w/GGST/Millia_Rage/Frame_Data"),
+            RosterEntry::new(CharacterId::CHIPP, "Chipp Zanuff", r"(?i)(chip)", "https://www.dustloop.com/w/GGST/Chipp_Zanuff/Frame_Data"),
+            RosterEntry::new(CharacterId::SOL, "Sol Badguy", r"(?i)(sol)", "https://www.dustloop.com/w/GGST/Sol_Badguy/Frame_Data"),
+            RosterEntry::new(CharacterId::KY, "Ky Kiske", r"(?i)(ky)", "https://www.dustloop.com/w/GGST/Ky_Kiske/Frame_Data"),
+            RosterEntry::new(CharacterId::MAY, "May", r"(?i)(may)", "https://www.dustloop.com/w/GGST/May/Frame_Data"),
+            RosterEntry::new(CharacterId::ZATO, "Zato-1", r"(?i)(zato)", "https://www.dustloop.com/w/GGST/Zato-1/Frame_Data"),
+            RosterEntry::new(CharacterId::INO, "I-No", r"(?i)(ino|i-no)", "https://www.dustloop.com/w/GGST/I-No/Frame_Data"),
+            RosterEntry::new(CharacterId::HAPPYCHAOS, "Happy Chaos", r"(?i)(hc|chaos|happy)", "https://www.dustloop.com/w/GGST/Happy_Chaos/Frame_Data"),
+            RosterEntry::new(CharacterId::SIN, "Sin Kiske", r"(?i)(sin)", "https://www.dustloop.com/w/GGST/Sin_Kiske/Frame_Data"),
+            RosterEntry::new(CharacterId::BAIKEN, "Baiken", r"(?i)(baiken)", "https://www.dustloop.com/w/GGST/Baiken/Frame_Data"),
+            RosterEntry::new(CharacterId::ANJI, "Anji Mito", r"(?i)(anji)", "https://www.dustloop.com/w/GGST/Anji_Mito/Frame_Data"),
+            RosterEntry::new(CharacterId::LEO, "Leo Whitefang", r"(?i)(leo)", "https://www.dustloop.com/w/GGST/Leo_Whitefang/Frame_Data"),
+            RosterEntry::new(CharacterId::FAUST, "Faust", r"(?i)(faust)", "https://www.dustloop.com/w/GGST/Faust/Frame_Data"),
+            RosterEntry::new(CharacterId::AXL, "Axl Low", r"(?i)(axl)", "https://www.dustloop.com/w/GGST/Axl_Low/Frame_Data"),
+            RosterEntry::new(CharacterId::POTEMKIN, "Potemkin", r"(?i)(pot)", "https://www.dustloop.com/w/GGST/Potemkin/Frame_Data"),
+            RosterEntry::new(CharacterId::RAMLETHAL, "Ramlethal Valentine", r"(?i)(ram)", "https://www.dustloop.com/w/GGST/Ramlethal_Valentine/Frame_Data"),
+            RosterEntry::new(CharacterId::GIO, "Giovanna", r"(?i)(gio)", "https://www.dustloop.com/w/GGST/Giovanna/Frame_Data"),
+            RosterEntry::new(CharacterId::GOLDLEWIS, "Goldlewis Dickinson", r"(?i)(lewis|gold|goldlewis|gl|dick)", "https://www.dustloop.com/w/GGST/Goldlewis_Dickinson/Frame_Data"),
+            RosterEntry::new(CharacterId::BRIDGET, "Bridget", r"(?i)(bridget)", "https://www.dustloop.com/w/GGST/Bridget/Frame_Data"),
+            RosterEntry::new(CharacterId::BEDMAN, "Bedman?", r"(?i)(bed)", "https://www.dustloop.com/w/GGST/Bedman/Frame_Data"),
+            RosterEntry::new(CharacterId::ASUKA, "Asuka R", r"(?i)(asuka)", "https://www.dustloop.com/w/GGST/Asuka_R/Frame_Data"),
+            RosterEntry::new(CharacterId::JOHNNY, "Johnny", r"(?i)(joh?nn?y)", "https://www.dustloop.com/w/GGST/Johnny/Frame_Data"),
+            RosterEntry::new(CharacterId::ELPHELT, "Elphelt Valentine", r"(?i)(el)", "https://www.dustloop.com/w/GGST/Elphelt_Valentine/Frame_Data"),
+            RosterEntry::new(CharacterId::ABA, "A.B.A", r"(?i)(a.?b.?a.?)", "https://www.dustloop.com/w/GGST/A.B.A/Frame_Data"),
+        ];
+        Roster { entries }
+    }
+
+    /// Adds the entries of `other`, replacing entries with the same id
+    pub fn merge(&mut self, other: Roster) {
+        for entry in other.entries {
+            match self.entries.iter_mut().find(|e| e.id == entry.id) {
+                Some(existing) => *existing = entry,
+                None => self.entries.push(entry),
+            }
+        }
+    }
+
+    /// Finds the entry best matching the query the same way [`crate::GGSTDLData::find_character`] finds a character
+    pub fn find_entry(&self, query: &str) -> Result<&RosterEntry, GGSTDLError> {
+        search::best_character(&self.entries, query, |e| search::score_entry(e, query), |e| &e.id, |e| &e.name)
+    }
+
+    /// The built in roster merged with the roster file at `path`, see [`Roster::from_file`]
+    pub async fn builtin_with_file(path: impl AsRef<Path>) -> Result<Roster, GGSTDLError> {
+        let mut roster = Roster::builtin();
+        roster.merge(Roster::from_file(path).await?);
+        Ok(roster)
+    }
+
+    /// Reads a roster file, TOML if the extension is `.toml` (needs the `toml` feature) and JSON (needs the `serde` feature) otherwise
+    pub async fn from_file(path: impl AsRef<Path>) -> Result<Roster, GGSTDLError> {
+        let path = path.as_ref();
+        let text = tokio::fs::read_to_string(path).await
+            .map_err(|e| GGSTDLError::Io { path: path.display().to_string(), message: e.to_string() })?;
+        if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("toml")) {
+            Roster::from_toml(&text)
+        } else {
+            Roster::from_json(&text)
+        }
+    }
+
+    /// Reads a JSON roster file:
+    /// `{ "characters": [{ "id": "SLAYER", "name": "Slayer", "aliases": ["slayer", "dandy"], "url": "https://www.dustloop.com/w/GGST/Slayer/Frame_Data" }] }`.
+    /// Instead of `aliases` an entry can give a `regex` directly.
+    pub fn from_json(text: &str) -> Result<Roster, GGSTDLError> {
+        #[cfg(feature = "serde")]
+        {
+            let file: file::RosterFile = serde_json::from_str(text).map_err(|e| GGSTDLError::InvalidJson { message: e.to_string() })?;
+            Ok(file.into_roster())
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = text;
+            Err(GGSTDLError::InvalidJson { message: String::from("reading JSON roster files needs the serde feature") })
+        }
+    }
+
+    /// Reads a TOML roster file with the same fields as [`Roster::from_json`], one `[[characters]]` table per character
+    pub fn from_toml(text: &str) -> Result<Roster, GGSTDLError> {
+        #[cfg(feature = "toml")]
+        {
+            let file: file::RosterFile = toml::from_str(text).map_err(|e| GGSTDLError::InvalidToml { message: e.to_string() })?;
+            Ok(file.into_roster())
+        }
+        #[cfg(not(feature = "toml"))]
+        {
+            let _ = text;
+            Err(GGSTDLError::InvalidToml { message: String::from("reading TOML roster files needs the toml feature") })
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod file {
+    use serde::Deserialize;
+
+    use super::{Roster, RosterEntry};
+    use crate::CharacterId;
+
+    #[derive(Deserialize)]
+    pub struct RosterFile {
+        characters: Vec<RosterFileEntry>,
+    }
+
+    #[derive(Deserialize)]
+    struct RosterFileEntry {
+        id: String,
+        name: String,
+        #[serde(default)]
+        aliases: Vec<String>,
+        regex: Option<String>,
+        url: String,
+    }
+
+    impl RosterFile {
+        pub fn into_roster(self) -> Roster {
+            let entries = self.characters.into_iter().map(|c| {
+                let regex = c.regex.unwrap_or_else(|| {
+                    let mut names = c.aliases.iter().map(|a| regex::escape(a)).collect::<Vec<String>>();
+                    names.push(regex::escape(&c.name));
+                    format!("(?i)({})", names.join("|"))
+                });
+                RosterEntry::new(CharacterId::parse(&c.id), c.name, regex, c.url)
+            }).collect();
+            Roster { entries }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn roster_file_merge() {
+    let file = Roster::from_json(r#"{ "characters": [
+        { "id": "SLAYER", "name": "Slayer", "aliases": ["dandy"], "url": "https://www.dustloop.com/w/GGST/Slayer/Frame_Data" },
+        { "id": "sol", "name": "Sol Badguy", "regex": "(?i)(sol|order)", "url": "https://www.dustloop.com/w/GGST/Sol_Badguy/Frame_Data" }
+    ] }"#).unwrap();
+    assert_eq!(file.entries[0].id, CharacterId::Other(String::from("SLAYER")));
+    assert_eq!(file.entries[0].regex, "(?i)(dandy|Slayer)");
+
+    let mut roster = Roster::builtin();
+    roster.merge(file);
+    assert_eq!(roster.entries.len(), CharacterId::ALL.len() + 1);
+    assert_eq!(roster.entries.iter().find(|e| e.id == CharacterId::SOL).unwrap().regex, "(?i)(sol|order)");
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn roster_file_toml() {
+    let roster = Roster::from_toml(r#"
+        [[characters]]
+        id = "SLAYER"
+        name = "Slayer"
+        aliases = ["dandy"]
+        url = "https://www.dustloop.com/w/GGST/Slayer/Frame_Data"
+    "#).unwrap();
+    assert_eq!(roster.entries[0].name, "Slayer");
+}
+
+#[test]
+fn builtin_frame_data_urls() {
+    assert!(Roster::builtin().entries.iter().all(|e| e.frame_data_url.ends_with("/Frame_Data")));
+}
@@ -0,0 +1,318 @@
+use regex::Regex;
+
+use crate::{AliasPriority, Character, CharacterId, GGSTDLData, GGSTDLError, Move, MoveAlias, MoveAliases, RosterEntry};
+
+/// Score of a move whose input is exactly the query, e.g. `236K`
+pub const SCORE_INPUT: u32 = 100;
+/// Score of a move whose name is exactly the query, e.g. `Gun Flame`
+pub const SCORE_NAME: u32 = 90;
+/// Score of a move whose alias regex matches the query, e.g. `dp`
+pub const SCORE_ALIAS: u32 = 80;
+/// Score of a move whose name contains the query, e.g. `viper`
+pub const SCORE_SUBSTRING: u32 = 60;
+/// Score of a move whose input or name is one edit away from the query, each further edit costs 10
+pub const SCORE_EDIT_DISTANCE: u32 = 40;
+
+/// Where the alias regex of a move comes from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AliasSource {
+    /// One of the built in aliases for the character
+    Binding,
+    /// No alias was found, the regex only matches the input itself (`c.S` also as `cS`)
+    #[default]
+    DefaultResolver,
+    /// One of the user's [`MoveAliases`]. Only set on a [`MoveResolution`], loaded moves never have it.
+    UserAlias,
+    /// No alias fired, the translated query is exactly the move's input. Only set on a [`MoveResolution`].
+    ExactInput,
+}
+
+/// Why [`GGSTDLData::find_move`] picked a move, see [`GGSTDLData::resolve_move_explained`]
+#[derive(Debug, Clone)]
+pub struct MoveResolution<'a> {
+    pub found: &'a Move,
+    /// The query that matched, the numpad translation (see [`crate::to_numpad`]) when `translated`
+    pub query: String,
+    pub translated: bool,
+    /// The alias pattern that matched the query, `None` when the translated query is exactly the move's input
+    pub pattern: Option<String>,
+    /// Where `pattern` comes from, [`AliasSource::ExactInput`] when there is none
+    pub source: AliasSource,
+    /// The other moves the query matched, in page order. They lose to `found` because they come later or, for a
+    /// translated query, because `found` has exactly that input.
+    pub other_matches: Vec<&'a Move>,
+}
+
+impl GGSTDLData {
+    /// Finds the same move as [`GGSTDLData::find_move`] and explains the match, for debugging alias tables
+    pub fn resolve_move_explained(&self, char_query: &str, move_query: &str) -> Result<MoveResolution<'_>, GGSTDLError> {
+        self.resolve_move_explained_with_aliases(char_query, move_query, &MoveAliases::default())
+    }
+
+    /// [`GGSTDLData::resolve_move_explained`] with user aliases, see [`GGSTDLData::find_move_with_aliases`]
+    pub fn resolve_move_explained_with_aliases(&self, char_query: &str, move_query: &str, aliases: &MoveAliases) -> Result<MoveResolution<'_>, GGSTDLError> {
+        resolve_move(self.find_character(char_query)?, aliases, move_query)
+    }
+
+    /// Every move of the character that could be meant by `move_query`, best match first.
+    /// Unlike [`GGSTDLData::find_move`] this lists all candidates, so ambiguous queries can be noticed.
+    pub fn search_moves(&self, char_query: &str, move_query: &str) -> Result<Vec<(u32, &Move)>, GGSTDLError> {
+        self.search_moves_with_aliases(char_query, move_query, &MoveAliases::default())
+    }
+
+    /// [`GGSTDLData::search_moves`] with user aliases, moves an alias points at score [`SCORE_ALIAS`]
+    pub fn search_moves_with_aliases(&self, char_query: &str, move_query: &str, aliases: &MoveAliases) -> Result<Vec<(u32, &Move)>, GGSTDLError> {
+        let character = self.find_character(char_query)?;
+        // `qcf K` scores like `236K` would
+        let numpad = crate::to_numpad(move_query);
+        let user_aliases = [AliasPriority::Override, AliasPriority::Fallback].into_iter()
+            .flat_map(|priority| aliases.matching(&character.id, priority, move_query))
+            .collect::<Vec<&MoveAlias>>();
+        let mut found = character.moves.iter()
+            .map(|m| {
+                let translated = numpad.as_ref().map(|numpad| score_move(m, numpad)).unwrap_or_default();
+                let aliased = if user_aliases.iter().any(|a| is_target(m, &a.target)) { SCORE_ALIAS } else { 0 };
+                (score_move(m, move_query).max(translated).max(aliased), m)
+            })
+            .filter(|(score, _)| *score > 0)
+            .collect::<Vec<(u32, &Move)>>();
+        // stable, so equally good matches keep their page order
+        found.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        Ok(found)
+    }
+}
+
+// override aliases, then the numpad translation, then the built in aliases and last the fallback aliases
+pub(crate) fn resolve_move<'a>(character: &'a Character, aliases: &MoveAliases, move_query: &str) -> Result<MoveResolution<'a>, GGSTDLError> {
+    if let Some(resolution) = resolve_user_alias(character, aliases, AliasPriority::Override, move_query) {
+        return Ok(resolution);
+    }
+    let unknown = || GGSTDLError::UnknownMove { character: character.id.clone(), query: move_query.to_string() };
+    let translated = crate::to_numpad(move_query)
+        .map(|numpad| {
+            let mut matches = character.moves.iter()
+                .filter(|m| m.input.eq_ignore_ascii_case(&numpad) || m.regex.is_match(&numpad))
+                .collect::<Vec<&Move>>();
+            // a move with exactly this input beats an alias that happens to contain it
+            matches.sort_by_key(|m| !m.input.eq_ignore_ascii_case(&numpad));
+            (numpad, matches)
+        })
+        .filter(|(_, matches)| !matches.is_empty());
+    let (query, matches, translated) = match translated {
+        Some((numpad, matches)) => (numpad, matches, true),
+        None => {
+            let matches = character.moves.iter().filter(|m| m.regex.is_match(move_query)).collect::<Vec<&Move>>();
+            (move_query.to_string(), matches, false)
+        },
+    };
+    let Some((found, other_matches)) = matches.split_first() else {
+        return resolve_user_alias(character, aliases, AliasPriority::Fallback, move_query).ok_or_else(unknown);
+    };
+    let pattern = Some(found.regex.as_str().to_string()).filter(|_| found.regex.is_match(&query));
+    Ok(MoveResolution {
+        found,
+        source: if pattern.is_some() { found.alias_source } else { AliasSource::ExactInput },
+        pattern,
+        query,
+        translated,
+        other_matches: other_matches.to_vec(),
+    })
+}
+
+// the moves targeted by the first user alias matching the query
+fn resolve_user_alias<'a>(character: &'a Character, aliases: &MoveAliases, priority: AliasPriority, move_query: &str) -> Option<MoveResolution<'a>> {
+    aliases.matching(&character.id, priority, move_query)
+        .find_map(|alias| {
+            let matches = character.moves.iter().filter(|m| is_target(m, &alias.target)).collect::<Vec<&Move>>();
+            let (found, other_matches) = matches.split_first()?;
+            Some(MoveResolution {
+                found,
+                query: move_query.to_string(),
+                translated: false,
+                pattern: Some(alias.regex.as_str().to_string()),
+                source: AliasSource::UserAlias,
+                other_matches: other_matches.to_vec(),
+            })
+        })
+}
+
+/// Whether `target`, the input or name an alias points at, is this move
+pub(crate) fn is_target(move_found: &Move, target: &str) -> bool {
+    move_found.input.eq_ignore_ascii_case(target) || move_found.name.eq_ignore_ascii_case(target)
+}
+
+pub(crate) fn score_move(move_found: &Move, query: &str) -> u32 {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return 0;
+    }
+    let input = move_found.input.to_lowercase();
+    let name = move_found.name.to_lowercase();
+    if input == query {
+        return SCORE_INPUT;
+    }
+    if name == query {
+        return SCORE_NAME;
+    }
+    if move_found.regex.is_match(&query) {
+        return SCORE_ALIAS;
+    }
+    if query.chars().count() > 2 && name.contains(&query) {
+        return SCORE_SUBSTRING;
+    }
+    let distance = edit_distance(&input, &query).min(edit_distance(&name, &query));
+    let allowed = (query.chars().count() / 3).max(1);
+    if distance <= allowed {
+        return SCORE_EDIT_DISTANCE.saturating_sub(10 * (distance as u32 - 1));
+    }
+    0
+}
+
+// exact name or id, then an alias matching the whole query, then a partial alias match (longer is better),
+// then the query appearing in the name
+pub(crate) fn score_character(character: &Character, query: &str) -> u32 {
+    score_names(&character.id, &character.name, &character.regex, query)
+}
+
+fn score_names(id: &CharacterId, name: &str, regex: &Regex, query: &str) -> u32 {
+    let simplify = |s: &str| s.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    let query = query.trim();
+    let simple_query = simplify(query);
+    if simple_query.is_empty() {
+        return 0;
+    }
+    if simple_query == simplify(name) || simple_query == simplify(&id.to_string()) {
+        return 3000;
+    }
+    if let Some(found) = regex.find_iter(query).map(|m| m.len()).max() {
+        if found == query.len() {
+            return 2000;
+        }
+        return 1000 + found as u32;
+    }
+    if name.to_lowercase().contains(&query.to_lowercase()) {
+        return 500;
+    }
+    0
+}
+
+// a roster entry is scored like the character it loads into, an invalid regex only misses the alias scores
+pub(crate) fn score_entry(entry: &RosterEntry, query: &str) -> u32 {
+    let regex = Regex::new(&entry.regex).unwrap_or_else(|_| Regex::new("$^").unwrap());
+    score_names(&entry.id, &entry.name, &regex, query)
+}
+
+/// The best scored candidate, several candidates sharing the best score is an [`GGSTDLError::AmbiguousQuery`]
+pub(crate) fn best_character<'a, T>(candidates: &'a [T], query: &str, score: impl Fn(&T) -> u32,
+                                    id: impl Fn(&T) -> &CharacterId, name: impl Fn(&T) -> &str) -> Result<&'a T, GGSTDLError> {
+    let mut found = candidates.iter()
+        .map(|c| (score(c), c))
+        .filter(|(score, _)| *score > 0)
+        .collect::<Vec<(u32, &T)>>();
+    found.sort_by(|(score_a, a), (score_b, b)| score_b.cmp(score_a).then_with(|| id(a).cmp(id(b))));
+    match found.as_slice() {
+        [] => Err(GGSTDLError::UnknownCharacter { query: query.to_string() }),
+        [(best, character), rest @ ..] if rest.first().is_none_or(|(score, _)| score < best) => Ok(character),
+        [(best, _), ..] => Err(GGSTDLError::AmbiguousQuery {
+            query: query.to_string(),
+            candidates: found.iter().filter(|(score, _)| score == best).map(|(_, c)| name(c).to_string()).collect(),
+        }),
+    }
+}
+
+// levenshtein distance over chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[tokio::test]
+async fn explain_resolution() {
+    let load = crate::load_sol_fixture().await;
+
+    let resolution = load.resolve_move_explained("sol", "dp").unwrap();
+    assert_eq!(resolution.found.input, "623H");
+    assert_eq!(resolution.source, AliasSource::Binding);
+    assert!(!resolution.translated && resolution.other_matches.is_empty());
+
+    // Bandit Revolver's alias also ends in 236K, the exact input wins
+    let resolution = load.resolve_move_explained("sol", "41236K").unwrap();
+    assert_eq!(resolution.found.name, "Fafnir");
+    assert_eq!((resolution.pattern, resolution.source), (None, AliasSource::ExactInput));
+    assert_eq!(resolution.other_matches.iter().map(|m| m.name.as_str()).collect::<Vec<&str>>(), vec!["Bandit Revolver"]);
+
+    let resolution = load.resolve_move_explained("sol", "5p").unwrap();
+    assert_eq!(resolution.source, AliasSource::DefaultResolver);
+    assert_eq!(resolution.pattern.as_deref(), Some("(?i)^(5P)$"));
+
+    let resolution = load.resolve_move_explained("sol", "qcf P").unwrap();
+    assert_eq!((resolution.query.as_str(), resolution.translated), ("236P", true));
+}
+
+#[test]
+fn edit_distances() {
+    assert_eq!(edit_distance("gun flame", "gun flame"), 0);
+    assert_eq!(edit_distance("gun flame", "gunflame"), 1);
+    assert_eq!(edit_distance("volcanic viper", "volcanic vipr"), 1);
+    assert_eq!(edit_distance("", "abc"), 3);
+}
+
+#[tokio::test]
+async fn search_ranks_candidates() {
+    let load = crate::load_sol_fixture().await;
+
+    let found = load.search_moves("sol", "viper").unwrap();
+    assert_eq!(found.len(), 2);
+    assert!(found.iter().all(|(score, m)| *score == SCORE_SUBSTRING && m.name == "Volcanic Viper"));
+
+    let found = load.search_moves("sol", "236K").unwrap();
+    assert_eq!(found[0].0, SCORE_INPUT);
+    assert_eq!(found[0].1.name, "Bandit Revolver");
+
+    let found = load.search_moves("sol", "gun flam").unwrap();
+    assert_eq!(found[0].1.name, "Gun Flame");
+    assert!(load.search_moves("sol", "nothing like it").unwrap().is_empty());
+
+    let found = load.search_moves("sol", "qcf k").unwrap();
+    assert_eq!((found[0].0, found[0].1.name.as_str()), (SCORE_INPUT, "Bandit Revolver"));
+}
+
+#[tokio::test]
+async fn user_aliases() {
+    let load = crate::load_sol_fixture().await;
+    let mut aliases = MoveAliases::new();
+    aliases.add(crate::CharacterId::SOL, "pilebunker", "Fafnir", AliasPriority::Override);
+    aliases.add(crate::CharacterId::SOL, "cs", "c.S", AliasPriority::Override);
+    aliases.add(crate::CharacterId::SOL, "dp", "Gun Flame", AliasPriority::Fallback);
+    aliases.add_pattern(crate::CharacterId::SOL, r"(?i)^gf$", "236P", AliasPriority::Fallback).unwrap();
+    assert!(aliases.add_pattern(crate::CharacterId::SOL, "(", "236P", AliasPriority::Fallback).is_err());
+
+    assert_eq!(load.find_move_with_aliases("sol", "PileBunker", &aliases).unwrap().name, "Fafnir");
+    assert_eq!(load.find_move_with_aliases("sol", "cs", &aliases).unwrap().input, "c.S");
+    assert_eq!(load.find_move_with_aliases("sol", "gf", &aliases).unwrap().name, "Gun Flame");
+    let resolution = load.resolve_move_explained_with_aliases("sol", "pilebunker", &aliases).unwrap();
+    assert_eq!(resolution.source, AliasSource::UserAlias);
+    // a fallback alias loses to the built in one
+    assert_eq!(load.find_move_with_aliases("sol", "dp", &aliases).unwrap().input, "623H");
+    aliases.add(crate::CharacterId::SOL, "dp", "Gun Flame", AliasPriority::Override);
+    assert_eq!(load.find_move_with_aliases("sol", "dp", &aliases).unwrap().name, "Gun Flame");
+
+    let found = load.search_moves_with_aliases("sol", "pilebunker", &aliases).unwrap();
+    assert_eq!((found[0].0, found[0].1.name.as_str()), (SCORE_ALIAS, "Fafnir"));
+    aliases.add(crate::CharacterId::SOL, "nothing", "No Such Move", AliasPriority::Override);
+    assert!(load.lint_with_aliases(&aliases).dead_bindings.iter().any(|d| d.target == "No Such Move"));
+    // a character that is not loaded at all, e.g. a typo in code that builds the id
+    let slayer = crate::CharacterId::Other(String::from("SLAYER"));
+    aliases.add(slayer.clone(), "mappa", "Mappa Hunch", AliasPriority::Override);
+    assert!(load.lint_with_aliases(&aliases).dead_bindings.iter().any(|d| d.character == slayer && d.target == "Mappa Hunch"));
+}
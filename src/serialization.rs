@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Character, GGSTDLData, GGSTDLError, LoadReport};
+
+/// Version of the document written by [`GGSTDLData::to_json`], bumped whenever the layout changes incompatibly
+pub const JSON_VERSION: u32 = 11;
+
+#[derive(Serialize)]
+struct JsonDocumentRef<'a> {
+    version: u32,
+    characters: &'a [Character],
+}
+
+#[derive(Deserialize)]
+struct JsonVersion {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct JsonDocument {
+    characters: Vec<Character>,
+}
+
+impl GGSTDLData {
+    /// Writes the data set as a versioned JSON document, alias regexes are stored as their patterns
+    pub fn to_json(&self) -> Result<String, GGSTDLError> {
+        let document = JsonDocumentRef { version: JSON_VERSION, characters: &self.characters };
+        serde_json::to_string(&document).map_err(|e| GGSTDLError::InvalidJson { message: e.to_string() })
+    }
+
+    /// Reads a data set written by [`GGSTDLData::to_json`]. The result has an empty [`LoadReport`] as nothing was scraped.
+    pub fn from_json(json: &str) -> Result<GGSTDLData, GGSTDLError> {
+        let version: JsonVersion = serde_json::from_str(json).map_err(|e| GGSTDLError::InvalidJson { message: e.to_string() })?;
+        if version.version != JSON_VERSION {
+            return Err(GGSTDLError::UnsupportedJsonVersion { version: version.version });
+        }
+        let document: JsonDocument = serde_json::from_str(json).map_err(|e| GGSTDLError::InvalidJson { message: e.to_string() })?;
+        Ok(GGSTDLData {
+            characters: document.characters,
+            report: LoadReport::default(),
+        })
+    }
+}
+
+pub mod regex_serde {
+    use regex::Regex;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(regex: &Regex, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(regex.as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+#[tokio::test]
+async fn json_round_trip() {
+    let load = crate::load_sol_fixture().await;
+
+    let json = load.to_json().unwrap();
+    let read = GGSTDLData::from_json(&json).unwrap();
+    assert_eq!(read.characters.len(), load.characters.len());
+    let found = read.find_move("sol", "vortex").unwrap();
+    assert_eq!(found.name, "Night Raid Vortex");
+    assert_eq!(found.startup_frames, load.find_move("sol", "vortex").unwrap().startup_frames);
+
+    let future = json.replacen(&format!("\"version\":{}", JSON_VERSION), "\"version\":999", 1);
+    assert_eq!(GGSTDLData::from_json(&future).unwrap_err(), GGSTDLError::UnsupportedJsonVersion { version: 999 });
+}
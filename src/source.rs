@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use crate::{CharacterId, GGSTDLError};
+
+pub type FetchResult = Result<Page, GGSTDLError>;
+
+/// A fetched page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    pub body: String,
+    /// The HTTP status, only set by sources that fetch over HTTP
+    pub status: Option<u16>,
+    /// Validators for conditional requests, only set by sources that fetch over HTTP
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Page {
+    pub fn new(body: impl Into<String>) -> Page {
+        Page { body: body.into(), status: None, etag: None, last_modified: None }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status.is_none_or(|s| (200..300).contains(&s))
+    }
+}
+
+/// A page to fetch, the character is set when the page belongs to one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageRequest {
+    pub url: String,
+    pub character: Option<CharacterId>,
+    /// Which of the character's pages this is, always [`PageKind::FrameData`] for pages without a character
+    pub kind: PageKind,
+}
+
+/// The pages the wiki has for each character
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PageKind {
+    /// The `<Character>/Frame_Data` page
+    #[default]
+    FrameData,
+    /// The `<Character>` page, holding the character's stats
+    Overview,
+}
+
+impl PageRequest {
+    pub fn url(url: impl Into<String>) -> PageRequest {
+        PageRequest { url: url.into(), character: None, kind: PageKind::FrameData }
+    }
+
+    pub fn character(character: CharacterId, url: impl Into<String>) -> PageRequest {
+        PageRequest { url: url.into(), character: Some(character), kind: PageKind::FrameData }
+    }
+
+    pub fn overview(character: CharacterId, url: impl Into<String>) -> PageRequest {
+        PageRequest { url: url.into(), character: Some(character), kind: PageKind::Overview }
+    }
+}
+
+/// What is known about a previously fetched copy of a page, see [`PageSource::fetch_if_modified`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Revalidated {
+    Modified(Page),
+    NotModified,
+}
+
+/// Somewhere the dustloop pages can be fetched from
+#[async_trait]
+pub trait PageSource: Send + Sync {
+    async fn fetch(&self, request: &PageRequest) -> FetchResult;
+
+    /// Fetches the page only if it changed since the copy described by `validators`.
+    /// Sources that cannot tell simply fetch it again.
+    async fn fetch_if_modified(&self, request: &PageRequest, _validators: &Validators) -> Result<Revalidated, GGSTDLError> {
+        self.fetch(request).await.map(Revalidated::Modified)
+    }
+}
+
+/// Fetches pages over HTTP, usually straight from dustloop.com
+#[derive(Debug, Clone, Default)]
+pub struct HttpSource {
+    client: reqwest::Client,
+}
+
+impl HttpSource {
+    pub fn new() -> HttpSource {
+        HttpSource::default()
+    }
+
+    /// Uses a preconfigured client, e.g. one with a proxy or custom user agent
+    pub fn with_client(client: reqwest::Client) -> HttpSource {
+        HttpSource { client }
+    }
+}
+
+#[async_trait]
+impl PageSource for HttpSource {
+    async fn fetch(&self, request: &PageRequest) -> FetchResult {
+        match self.fetch_if_modified(request, &Validators::default()).await? {
+            Revalidated::Modified(page) => Ok(page),
+            // without validators the server has nothing to compare against, so this should never happen
+            Revalidated::NotModified => Err(GGSTDLError::Http { url: request.url.clone(), status: 304 }),
+        }
+    }
+
+    async fn fetch_if_modified(&self, request: &PageRequest, validators: &Validators) -> Result<Revalidated, GGSTDLError> {
+        let fetch_error = |e: reqwest::Error| GGSTDLError::Fetch { url: request.url.clone(), message: e.to_string() };
+        let mut builder = self.client.get(request.url.as_str());
+        if let Some(etag) = &validators.etag {
+            builder = builder.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            builder = builder.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        let res = builder.send().await.map_err(fetch_error)?;
+        if res.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Revalidated::NotModified);
+        }
+        let header = |name: reqwest::header::HeaderName| res.headers().get(name).and_then(|v| v.to_str().ok()).map(String::from);
+        let etag = header(reqwest::header::ETAG);
+        let last_modified = header(reqwest::header::LAST_MODIFIED);
+        let status = res.status().as_u16();
+        Ok(Revalidated::Modified(Page { body: res.text().await.map_err(fetch_error)?, status: Some(status), etag, last_modified }))
+    }
+}
+
+/// Reads pages saved with [`crate::snapshot`]. Character pages are stored as `<CharacterId>.html` (e.g. `SOL.html`)
+/// and `<CharacterId>.overview.html`, anything else under a file name derived from its url.
+#[derive(Debug, Clone)]
+pub struct DirSource {
+    path: PathBuf,
+}
+
+impl DirSource {
+    pub fn new(path: impl AsRef<Path>) -> DirSource {
+        DirSource { path: path.as_ref().to_path_buf() }
+    }
+
+    pub fn file_name(request: &PageRequest) -> String {
+        match (&request.character, request.kind) {
+            (Some(id), PageKind::FrameData) => format!("{}.html", id),
+            (Some(id), PageKind::Overview) => format!("{}.overview.html", id),
+            (None, _) => {
+                let path = request.url.split_once("://").map(|(_, rest)| rest).unwrap_or(request.url.as_str());
+                let name = path.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect::<String>();
+                format!("{}.html", name.trim_matches('_'))
+            }
+        }
+    }
+
+    pub fn path_for(&self, request: &PageRequest) -> PathBuf {
+        self.path.join(DirSource::file_name(request))
+    }
+}
+
+#[async_trait]
+impl PageSource for DirSource {
+    async fn fetch(&self, request: &PageRequest) -> FetchResult {
+        let path = self.path_for(request);
+        tokio::fs::read_to_string(&path).await
+            .map(Page::new)
+            .map_err(|e| GGSTDLError::Io { path: path.display().to_string(), message: e.to_string() })
+    }
+}
+
+/// Serves pages held in memory, mostly useful for tests
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    characters: HashMap<(CharacterId, PageKind), String>,
+    urls: HashMap<String, String>,
+}
+
+impl MemorySource {
+    pub fn new() -> MemorySource {
+        MemorySource::default()
+    }
+
+    pub fn insert_character(&mut self, character: CharacterId, page: impl Into<String>) {
+        self.characters.insert((character, PageKind::FrameData), page.into());
+    }
+
+    pub fn insert_overview(&mut self, character: CharacterId, page: impl Into<String>) {
+        self.characters.insert((character, PageKind::Overview), page.into());
+    }
+
+    pub fn insert_url(&mut self, url: impl Into<String>, page: impl Into<String>) {
+        self.urls.insert(url.into(), page.into());
+    }
+}
+
+#[async_trait]
+impl PageSource for MemorySource {
+    async fn fetch(&self, request: &PageRequest) -> FetchResult {
+        request.character.as_ref().and_then(|id| self.characters.get(&(id.clone(), request.kind)))
+            .or(self.urls.get(&request.url))
+            .map(Page::new)
+            .ok_or_else(|| GGSTDLError::Fetch { url: request.url.clone(), message: String::from("No page stored") })
+    }
+}
+
+#[test]
+fn dir_source_file_names() {
+    let request = PageRequest::character(CharacterId::SOL, "https://www.dustloop.com/w/GGST/Sol_Badguy/Frame_Data");
+    assert_eq!(DirSource::file_name(&request), "SOL.html");
+    let request = PageRequest::overview(CharacterId::SOL, "https://www.dustloop.com/w/GGST/Sol_Badguy");
+    assert_eq!(DirSource::file_name(&request), "SOL.overview.html");
+    let request = PageRequest::url("https://www.dustloop.com/w/GGST");
+    assert_eq!(DirSource::file_name(&request), "www_dustloop_com_w_GGST.html");
+}
+
+#[tokio::test]
+async fn memory_source_lookup() {
+    let mut source = MemorySource::new();
+    source.insert_character(CharacterId::SOL, "sol page");
+    source.insert_overview(CharacterId::SOL, "sol overview");
+    source.insert_url("https://www.dustloop.com/w/GGST", "index page");
+    assert_eq!(source.fetch(&PageRequest::character(CharacterId::SOL, "unused")).await.unwrap().body, "sol page");
+    assert_eq!(source.fetch(&PageRequest::overview(CharacterId::SOL, "unused")).await.unwrap().body, "sol overview");
+    assert_eq!(source.fetch(&PageRequest::url("https://www.dustloop.com/w/GGST")).await.unwrap().body, "index page");
+    assert!(source.fetch(&PageRequest::character(CharacterId::KY, "unused")).await.is_err());
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{Character, FrameValue, GGSTDLError, PageRequest, PageSource};
+
+/// A character's stats and movement, as listed on their overview page
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterStats {
+    /// Damage taken is multiplied by this, e.g. `0.96`
+    pub defense: Option<f32>,
+    pub guts: Option<i32>,
+    pub weight: Option<f32>,
+    pub prejump: Option<i32>,
+    /// Total duration of the backdash
+    pub backdash: Option<i32>,
+    /// The frames of the backdash that are invulnerable, e.g. `1-7`
+    pub backdash_invuln: FrameValue,
+    pub walk_speed: Option<f32>,
+    pub back_walk_speed: Option<f32>,
+    pub dash_speed: Option<f32>,
+    /// Unique movement options, e.g. `Dash Cancel`
+    pub movement_options: Vec<String>,
+    /// Every label and value found on the page as text, including the ones read into the fields above
+    pub values: BTreeMap<String, String>,
+}
+
+fn get_stat_row_selector() -> &'static Selector {
+    static STAT_ROW_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    STAT_ROW_SELECTOR.get_or_init(|| Selector::parse("tr, dl").unwrap())
+}
+
+fn get_label_selector() -> &'static Selector {
+    static LABEL_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    LABEL_SELECTOR.get_or_init(|| Selector::parse("th, dt").unwrap())
+}
+
+fn get_value_selector() -> &'static Selector {
+    static VALUE_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    VALUE_SELECTOR.get_or_init(|| Selector::parse("td, dd").unwrap())
+}
+
+fn get_list_item_selector() -> &'static Selector {
+    static LIST_ITEM_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    LIST_ITEM_SELECTOR.get_or_init(|| Selector::parse("li").unwrap())
+}
+
+fn get_number_matcher() -> &'static Regex {
+    static NUMBER_MATCHER: OnceLock<Regex> = OnceLock::new();
+    NUMBER_MATCHER.get_or_init(|| Regex::new(r"-?\d+(?:\.\d+)?").unwrap())
+}
+
+fn get_invuln_matcher() -> &'static Regex {
+    static INVULN_MATCHER: OnceLock<Regex> = OnceLock::new();
+    INVULN_MATCHER.get_or_init(|| Regex::new(r"(?i)(\d+)\s*[-~]\s*(\d+)\s*(?:\w+\s+)?invuln").unwrap())
+}
+
+fn get_range_matcher() -> &'static Regex {
+    static RANGE_MATCHER: OnceLock<Regex> = OnceLock::new();
+    RANGE_MATCHER.get_or_init(|| Regex::new(r"(\d+)\s*[-~]\s*(\d+)").unwrap())
+}
+
+impl CharacterStats {
+    /// Reads the stats from an overview page. Labels are matched loosely (`Pre-jump`, `Prejump:`), so small layout
+    /// changes on the wiki don't lose them. Returns `None` when the page has none of the known stats.
+    pub fn parse(html: &str) -> Option<CharacterStats> {
+        let document = Html::parse_document(html);
+        let mut stats = CharacterStats::default();
+        let mut found = false;
+        for row in document.select(get_stat_row_selector()) {
+            // a definition list holds several pairs, a table row just one
+            let labels = row.select(get_label_selector()).filter(|e| is_direct_child(row, *e));
+            let values = row.select(get_value_selector()).filter(|e| is_direct_child(row, *e));
+            for (label, value) in labels.zip(values) {
+                let label_text = text(label).trim_end_matches(':').trim().to_string();
+                if label_text.is_empty() {
+                    continue;
+                }
+                found |= stats.read(&label_text, value);
+                stats.values.insert(label_text, text(value));
+            }
+        }
+        found.then_some(stats)
+    }
+
+    // sets the field the label is for, returns whether it was a known stat
+    fn read(&mut self, label: &str, value: ElementRef) -> bool {
+        let label = label.to_lowercase().chars().filter(|c| c.is_alphanumeric() || *c == ' ').collect::<String>();
+        let value_text = text(value);
+        match label.as_str() {
+            "defense" => self.defense = first_number(&value_text),
+            "guts" => self.guts = first_number(&value_text).map(|n| n as i32),
+            "weight" => self.weight = first_number(&value_text),
+            "prejump" => self.prejump = first_number(&value_text).map(|n| n as i32),
+            l if l.starts_with("backdash") && l.contains("invuln") => self.backdash_invuln = invuln(&value_text, true),
+            l if l.starts_with("backdash") => {
+                self.backdash = first_number(&value_text).map(|n| n as i32);
+                if self.backdash_invuln.is_empty() {
+                    self.backdash_invuln = invuln(&value_text, false);
+                }
+            },
+            l if l.contains("walk") && l.contains("back") => self.back_walk_speed = first_number(&value_text),
+            l if l.contains("walk") => self.walk_speed = first_number(&value_text),
+            l if l.contains("dash") && !l.contains("air") && !l.contains("back") => self.dash_speed = first_number(&value_text),
+            l if l.contains("movement") => {
+                let items = value.select(get_list_item_selector()).map(text).collect::<Vec<String>>();
+                self.movement_options = if items.is_empty() {
+                    value_text.split([',', ';']).map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+                } else {
+                    items
+                };
+            },
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Fetches the character's overview page from `source` and reads their stats from it
+pub(crate) async fn get_stats(character: &Character, source: &dyn PageSource) -> Result<CharacterStats, GGSTDLError> {
+    let request = PageRequest::overview(character.id.clone(), character.overview_url());
+    let page = source.fetch(&request).await?;
+    if !page.is_success() {
+        return Err(GGSTDLError::Http { url: request.url, status: page.status.unwrap_or_default() });
+    }
+    CharacterStats::parse(&page.body)
+        .ok_or_else(|| GGSTDLError::MissingSection { character: character.id.clone(), section: String::from("stats") })
+}
+
+pub(crate) fn overview_url(frame_data_url: &str) -> String {
+    frame_data_url.trim_end_matches('/').trim_end_matches("/Frame_Data").to_string()
+}
+
+fn is_direct_child(parent: ElementRef, child: ElementRef) -> bool {
+    child.parent().is_some_and(|p| p.id() == parent.id())
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect::<Vec<&str>>().join(" ").split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn first_number(text: &str) -> Option<f32> {
+    get_number_matcher().find(text).and_then(|m| m.as_str().parse().ok())
+}
+
+// `1-7 invuln` inside a longer value, or just `1-7` when the label already says it's the invulnerability
+fn invuln(text: &str, labelled: bool) -> FrameValue {
+    let matcher = if labelled { get_range_matcher() } else { get_invuln_matcher() };
+    match matcher.captures(text) {
+        Some(captures) => FrameValue::Range(captures[1].parse().unwrap_or_default(), captures[2].parse().unwrap_or_default()),
+        None if labelled => FrameValue::parse(text),
+        None => FrameValue::Empty,
+    }
+}
+
+#[test]
+fn stats_labels() {
+    let html = r#"<table>
+        <tr><th>Defense:</th><td>0.96</td></tr>
+        <tr><th>Pre-jump</th><td>4</td></tr>
+        <tr><th>Backdash</th><td>23</td></tr>
+        <tr><th>Backdash Invuln</th><td>1-8</td></tr>
+        <tr><td>not a stat</td><td>ignored</td></tr>
+    </table>
+    <dl><dt>Unique Movement Options</dt><dd>Dash Cancel, Double Jump</dd></dl>"#;
+    let stats = CharacterStats::parse(html).unwrap();
+    assert_eq!(stats.defense, Some(0.96));
+    assert_eq!(stats.prejump, Some(4));
+    assert_eq!(stats.backdash, Some(23));
+    assert_eq!(stats.backdash_invuln, FrameValue::Range(1, 8));
+    assert_eq!(stats.movement_options, vec!["Dash Cancel", "Double Jump"]);
+    assert_eq!(stats.values.get("Defense").map(String::as_str), Some("0.96"));
+    assert!(CharacterStats::parse("<table><tr><th>Damage</th><td>30</td></tr></table>").is_none());
+}
@@ -0,0 +1,68 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+// community motion names, longest first so `dqcf` is not read as `d` + `qcf`
+const MOTIONS: [(&str, &str); 12] = [
+    ("qcfqcf", "236236"),
+    ("qcbqcb", "214214"),
+    ("dqcf", "236236"),
+    ("dqcb", "214214"),
+    ("hcbf", "632146"),
+    ("hcf", "41236"),
+    ("hcb", "63214"),
+    ("rdp", "421"),
+    ("qcf", "236"),
+    ("qcb", "214"),
+    ("srk", "623"),
+    ("dp", "623"),
+];
+
+fn get_query_matcher() -> &'static Regex {
+    static QUERY_MATCHER: OnceLock<Regex> = OnceLock::new();
+    QUERY_MATCHER.get_or_init(|| {
+        let motions = MOTIONS.iter().map(|(name, _)| *name).collect::<Vec<&str>>().join("|");
+        Regex::new(&format!(
+            r"^(?:(j|c|f)\s*\.?\s*)?((?:{})|[1-9]+)?\s*\+?\s*([\[{{]?)(hs|[pkshd])([\]}}]?)$",
+            motions
+        )).unwrap()
+    })
+}
+
+/// Translates a move query written with community motion names into numpad notation, e.g. `qcf K` to `236K`,
+/// `dp S` to `623S`, `j. qcb H` to `j.214H` and `5HS` to `5H`. `None` when the query is not a motion and button,
+/// like a move name or a bare `dp`, so it should be matched as written.
+pub fn to_numpad(query: &str) -> Option<String> {
+    let query = query.trim().to_lowercase().split_whitespace().collect::<Vec<&str>>().join(" ");
+    let captures = get_query_matcher().captures(&query)?;
+    let prefix = captures.get(1).map(|p| p.as_str());
+    let motion = captures.get(2).map(|m| m.as_str());
+    // a lone button is not a move, `d` or `hs` are more likely part of a name
+    if prefix.is_none() && motion.is_none() {
+        return None;
+    }
+    let (open, close) = (&captures[3], &captures[5]);
+    if !matches!((open, close), ("", "") | ("[", "]") | ("{", "}")) {
+        return None;
+    }
+    let motion = motion
+        .map(|m| MOTIONS.iter().find(|(name, _)| *name == m).map(|(_, numpad)| *numpad).unwrap_or(m))
+        .unwrap_or_default();
+    let button = if &captures[4] == "hs" { "H" } else { &captures[4] }.to_uppercase();
+    let prefix = prefix.map(|p| format!("{}.", p)).unwrap_or_default();
+    Some(format!("{}{}{}{}{}", prefix, motion, open, button, close))
+}
+
+#[test]
+fn translate_queries() {
+    assert_eq!(to_numpad("qcf K").as_deref(), Some("236K"));
+    assert_eq!(to_numpad("dp S").as_deref(), Some("623S"));
+    assert_eq!(to_numpad("hcb P").as_deref(), Some("63214P"));
+    assert_eq!(to_numpad("j. qcb H").as_deref(), Some("j.214H"));
+    assert_eq!(to_numpad("5HS").as_deref(), Some("5H"));
+    assert_eq!(to_numpad("qcf[hs]").as_deref(), Some("236[H]"));
+    assert_eq!(to_numpad("c.s").as_deref(), Some("c.S"));
+    assert_eq!(to_numpad("dp"), None);
+    assert_eq!(to_numpad("hs"), None);
+    assert_eq!(to_numpad("gun flame"), None);
+}